@@ -0,0 +1,40 @@
+//! Creating, adding, removing, and finding assertions (`envelope assertion`).
+
+use anyhow::{anyhow, Result};
+use bc_envelope::prelude::*;
+
+/// Create a bare assertion envelope, optionally salted.
+pub fn create(predicate: impl EnvelopeEncodable, object: impl EnvelopeEncodable, salted: bool) -> Envelope {
+    let assertion = Envelope::new_assertion(predicate, object);
+    if salted { assertion.add_salt() } else { assertion }
+}
+
+/// Add an assertion envelope to an envelope, optionally salting it first.
+pub fn add(envelope: &Envelope, assertion: Envelope, salted: bool) -> Result<Envelope> {
+    envelope.clone().add_assertion_envelope_salted(assertion, salted)
+}
+
+/// Remove an assertion from an envelope. The envelope is returned unchanged
+/// if it does not have the assertion.
+pub fn remove(envelope: &Envelope, assertion: Envelope) -> Envelope {
+    envelope.clone().remove_assertion(assertion)
+}
+
+/// The assertion at the given index.
+pub fn at(envelope: &Envelope, index: usize) -> Result<Envelope> {
+    envelope.assertions().get(index).cloned().ok_or_else(|| anyhow!("Index out of bounds"))
+}
+
+/// The assertions whose predicate has the same digest as `predicate`.
+pub fn find_by_predicate(envelope: &Envelope, predicate: &Envelope) -> Vec<Envelope> {
+    envelope.assertions().into_iter()
+        .filter(|assertion| assertion.as_predicate().is_some_and(|p| p.digest() == predicate.digest()))
+        .collect()
+}
+
+/// The assertions whose object has the same digest as `object`.
+pub fn find_by_object(envelope: &Envelope, object: &Envelope) -> Vec<Envelope> {
+    envelope.assertions().into_iter()
+        .filter(|assertion| assertion.as_object().is_some_and(|o| o.digest() == object.digest()))
+        .collect()
+}
@@ -0,0 +1,23 @@
+//! Compressing and uncompressing envelopes (`envelope compress` and
+//! `envelope uncompress`).
+
+use anyhow::Result;
+use bc_envelope::prelude::*;
+
+/// Compress the envelope, or only its subject if `subject` is `true`.
+pub fn compress(envelope: &Envelope, subject: bool) -> Result<Envelope> {
+    if subject {
+        envelope.compress_subject()
+    } else {
+        envelope.compress()
+    }
+}
+
+/// Uncompress the envelope, or only its subject if `subject` is `true`.
+pub fn uncompress(envelope: &Envelope, subject: bool) -> Result<Envelope> {
+    if subject {
+        envelope.uncompress_subject()
+    } else {
+        envelope.uncompress()
+    }
+}
@@ -0,0 +1,179 @@
+//! Structural comparison of two envelopes (`envelope diff`).
+
+use std::collections::HashSet;
+
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*};
+
+/// The kind of a difference between two envelopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifferenceKind {
+    /// An assertion is only in the second envelope.
+    Added,
+    /// An assertion is only in the first envelope.
+    Removed,
+    /// An element was replaced by one with a different digest.
+    Changed,
+    /// An element was elided in the second envelope.
+    Elided,
+    /// An element was encrypted in the second envelope.
+    Encrypted,
+    /// An element was compressed in the second envelope.
+    Compressed,
+    /// An element that was obscured in the first envelope is not obscured in
+    /// the second.
+    Revealed,
+}
+
+impl DifferenceKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifferenceKind::Added => "added",
+            DifferenceKind::Removed => "removed",
+            DifferenceKind::Changed => "changed",
+            DifferenceKind::Elided => "elided",
+            DifferenceKind::Encrypted => "encrypted",
+            DifferenceKind::Compressed => "compressed",
+            DifferenceKind::Revealed => "revealed",
+        }
+    }
+}
+
+/// A difference between two envelopes.
+#[derive(Debug, Clone)]
+pub struct Difference {
+    pub kind: DifferenceKind,
+    /// The location of the difference: `wrapped` for the content of a wrapped
+    /// envelope, the predicate of an assertion for its object, and `pred` for
+    /// the predicate itself.
+    pub path: Vec<String>,
+    /// The element in the first envelope, if any.
+    pub old: Option<Envelope>,
+    /// The element in the second envelope, if any.
+    pub new: Option<Envelope>,
+}
+
+impl Difference {
+    /// The path as a string, e.g. `/wrapped/"knows"`.
+    pub fn path_string(&self) -> String {
+        format!("/{}", self.path.join("/"))
+    }
+}
+
+/// Compare two envelopes, matching their elements by digest.
+///
+/// Assertions are matched by digest, then assertions with the same predicate
+/// are compared as changes to their objects. Elements with the same digest
+/// are compared for differences in elision, encryption, and compression.
+pub fn diff(old: &Envelope, new: &Envelope) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_elements(old, new, &[], &mut differences);
+    differences
+}
+
+fn diff_elements(old: &Envelope, new: &Envelope, path: &[String], differences: &mut Vec<Difference>) {
+    if old.structural_digest() == new.structural_digest() {
+        return;
+    }
+    let difference = |kind| Difference { kind, path: path.to_vec(), old: Some(old.clone()), new: Some(new.clone()) };
+    if old.digest() == new.digest() {
+        match (obscured_kind(old), obscured_kind(new)) {
+            (_, Some(kind)) => differences.push(difference(kind)),
+            (Some(_), None) => differences.push(difference(DifferenceKind::Revealed)),
+            (None, None) => diff_children(old, new, path, differences),
+        }
+        return;
+    }
+    if obscured_kind(old).is_some() || obscured_kind(new).is_some() {
+        differences.push(difference(DifferenceKind::Changed));
+        return;
+    }
+    match (old.case(), new.case()) {
+        (EnvelopeCase::Node { .. }, _) | (_, EnvelopeCase::Node { .. }) => diff_children(old, new, path, differences),
+        (EnvelopeCase::Wrapped { .. }, EnvelopeCase::Wrapped { .. })
+        | (EnvelopeCase::Assertion(_), EnvelopeCase::Assertion(_)) => diff_children(old, new, path, differences),
+        _ => differences.push(difference(DifferenceKind::Changed)),
+    }
+}
+
+/// Compare the children of two elements that are both nodes (or one node and
+/// a bare subject), both wrapped, or both assertions.
+fn diff_children(old: &Envelope, new: &Envelope, path: &[String], differences: &mut Vec<Difference>) {
+    match (old.case(), new.case()) {
+        (EnvelopeCase::Wrapped { envelope: old, .. }, EnvelopeCase::Wrapped { envelope: new, .. }) => {
+            diff_elements(old, new, &child_path(path, "wrapped"), differences);
+        }
+        (EnvelopeCase::Assertion(old), EnvelopeCase::Assertion(new)) => {
+            diff_elements(&old.predicate(), &new.predicate(), &child_path(path, "pred"), differences);
+            diff_elements(&old.object(), &new.object(), path, differences);
+        }
+        _ => {
+            diff_elements(&old.subject(), &new.subject(), path, differences);
+            diff_assertions(&old.assertions(), &new.assertions(), path, differences);
+        }
+    }
+}
+
+fn diff_assertions(old: &[Envelope], new: &[Envelope], path: &[String], differences: &mut Vec<Difference>) {
+    let mut unmatched_old = Vec::new();
+    let mut matched_new = HashSet::new();
+    for old_assertion in old {
+        match new.iter().position(|new_assertion| new_assertion.digest() == old_assertion.digest()) {
+            Some(index) => {
+                matched_new.insert(index);
+                diff_elements(old_assertion, &new[index], &child_path(path, &assertion_label(old_assertion)), differences);
+            }
+            None => unmatched_old.push(old_assertion),
+        }
+    }
+    let mut unmatched_new = new.iter().enumerate()
+        .filter(|(index, _)| !matched_new.contains(index))
+        .map(|(_, assertion)| assertion)
+        .collect::<Vec<_>>();
+
+    for old_assertion in unmatched_old {
+        // An assertion is changed if it is the only one in each envelope with
+        // its predicate.
+        let same_predicate = |assertion: &&Envelope| predicate_digest(assertion).is_some()
+            && predicate_digest(assertion) == predicate_digest(old_assertion);
+        let candidates = unmatched_new.iter().filter(|assertion| same_predicate(assertion)).count();
+        let old_count = old.iter().filter(|assertion| same_predicate(assertion)).count();
+        if candidates == 1 && old_count == 1 {
+            let index = unmatched_new.iter().position(same_predicate).unwrap();
+            let new_assertion = unmatched_new.remove(index);
+            diff_elements(old_assertion, new_assertion, &child_path(path, &assertion_label(old_assertion)), differences);
+        } else {
+            differences.push(Difference { kind: DifferenceKind::Removed, path: path.to_vec(), old: Some(old_assertion.clone()), new: None });
+        }
+    }
+    for new_assertion in unmatched_new {
+        differences.push(Difference { kind: DifferenceKind::Added, path: path.to_vec(), old: None, new: Some(new_assertion.clone()) });
+    }
+}
+
+fn obscured_kind(envelope: &Envelope) -> Option<DifferenceKind> {
+    match envelope.case() {
+        EnvelopeCase::Elided(_) => Some(DifferenceKind::Elided),
+        EnvelopeCase::Encrypted(_) => Some(DifferenceKind::Encrypted),
+        EnvelopeCase::Compressed(_) => Some(DifferenceKind::Compressed),
+        _ => None,
+    }
+}
+
+fn predicate_digest(assertion: &Envelope) -> Option<Digest> {
+    assertion.as_predicate().map(|predicate| predicate.digest().into_owned())
+}
+
+/// The path segment of an assertion: its predicate, or its short digest if
+/// the assertion is obscured.
+fn assertion_label(assertion: &Envelope) -> String {
+    match assertion.as_predicate() {
+        Some(predicate) => predicate.format_flat(),
+        None => assertion.short_id(),
+    }
+}
+
+fn child_path(path: &[String], segment: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(segment.to_string());
+    path
+}
@@ -0,0 +1,48 @@
+//! Eliding, encrypting, or compressing parts of an envelope
+//! (`envelope elide`).
+
+use std::collections::HashSet;
+
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*};
+
+/// Obscure the elements of the envelope whose digests are in `target`.
+pub fn elide_removing(envelope: &Envelope, target: &HashSet<Digest>, action: &ObscureAction) -> Envelope {
+    envelope.elide_set_with_action(target, false, action)
+}
+
+/// Obscure the elements of the envelope whose digests are not in `target`.
+pub fn elide_revealing(envelope: &Envelope, target: &HashSet<Digest>, action: &ObscureAction) -> Envelope {
+    envelope.elide_set_with_action(target, true, action)
+}
+
+/// The target set for `elide_revealing` that reveals each selected element
+/// and its contents, the elements leading to it, and the predicates of the
+/// assertions along the way.
+pub fn revealing_set(envelope: &Envelope, selected: &HashSet<Digest>) -> HashSet<Digest> {
+    let mut target = HashSet::new();
+    add_revealed(envelope, selected, &mut Vec::new(), &mut target);
+    target
+}
+
+fn add_revealed(element: &Envelope, selected: &HashSet<Digest>, ancestors: &mut Vec<Envelope>, target: &mut HashSet<Digest>) {
+    if selected.contains(&element.digest()) {
+        target.extend(element.deep_digests());
+        for ancestor in ancestors.iter() {
+            target.insert(ancestor.digest().into_owned());
+            if let Some(predicate) = ancestor.as_predicate() {
+                target.insert(predicate.digest().into_owned());
+            }
+        }
+    }
+    let children = match element.case() {
+        EnvelopeCase::Node { subject, assertions, .. } => [vec![subject.clone()], assertions.clone()].concat(),
+        EnvelopeCase::Wrapped { envelope, .. } => vec![envelope.clone()],
+        EnvelopeCase::Assertion(assertion) => vec![assertion.predicate(), assertion.object()],
+        _ => vec![],
+    };
+    ancestors.push(element.clone());
+    for child in &children {
+        add_revealed(child, selected, ancestors, target);
+    }
+    ancestors.pop();
+}
@@ -0,0 +1,344 @@
+//! Encrypting and decrypting an envelope's subject (`envelope encrypt` and
+//! `envelope decrypt`).
+//!
+//! A key can also be derived from a password with Argon2id. The salt and
+//! parameters of the derivation are recorded in an assertion on the
+//! encrypted envelope:
+//!
+//! ```text
+//! ENCRYPTED [
+//!     "kdf": "Argon2id" [
+//!         "iterations": 2
+//!         "memory": 19456
+//!         "parallelism": 1
+//!         'salt': Salt
+//!     ]
+//! ]
+//! ```
+//!
+//! Elements encrypted in place (`elide --action encrypt --password`) each
+//! carry the assertion, so they can be decrypted wherever the envelope is
+//! later wrapped or extended, for example by signing it. Like the assertion
+//! on an encrypted subject, this changes the envelope's digest.
+//!
+//! Since the parameters are read from the envelope, which may be untrusted,
+//! a derivation is refused if they exceed [`MAX_MEMORY`],
+//! [`MAX_ITERATIONS`], or [`MAX_PARALLELISM`].
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use bc_components::{Decrypter, Digest, EncapsulationScheme, PrivateKeyBase, PublicKeys, Salt, SealedMessage, SymmetricKey};
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*, EnvelopeError};
+use known_values::{HAS_RECIPIENT, SALT};
+
+/// The predicate of the assertion recording how a key was derived from a
+/// password.
+pub const KDF: &str = "kdf";
+const ARGON2ID: &str = "Argon2id";
+/// The memory cost, in KiB.
+const MEMORY: &str = "memory";
+const ITERATIONS: &str = "iterations";
+const PARALLELISM: &str = "parallelism";
+const SALT_LENGTH: usize = 16;
+
+/// The largest memory cost, in KiB (1 GiB), accepted from an envelope.
+pub const MAX_MEMORY: u32 = 1024 * 1024;
+/// The largest number of iterations accepted from an envelope.
+pub const MAX_ITERATIONS: u32 = 16;
+/// The largest degree of parallelism accepted from an envelope.
+pub const MAX_PARALLELISM: u32 = 16;
+
+/// Encrypt the envelope's subject with `key`, and add a `hasRecipient`
+/// assertion for each of the `recipients`.
+///
+/// If `key` is `None` an ephemeral key is generated, in which case there must
+/// be at least one recipient.
+pub fn encrypt(envelope: &Envelope, key: Option<&SymmetricKey>, recipients: &[PublicKeys]) -> Result<Envelope> {
+    let key = match key {
+        Some(key) => key.clone(),
+        None => {
+            if recipients.is_empty() {
+                bail!("Must provide either a key or recipients.");
+            }
+            SymmetricKey::new()
+        }
+    };
+    let mut encrypted = envelope.encrypt_subject(&key)?;
+    for recipient in recipients {
+        encrypted = encrypted.add_recipient(recipient, &key);
+    }
+    Ok(encrypted)
+}
+
+/// Decrypt the envelope's subject with a symmetric key.
+pub fn decrypt(envelope: &Envelope, key: &SymmetricKey) -> Result<Envelope> {
+    envelope.decrypt_subject(key)
+}
+
+/// Decrypt the envelope's subject as one of its recipients.
+pub fn decrypt_to_recipient(envelope: &Envelope, recipient: &PrivateKeyBase) -> Result<Envelope> {
+    envelope.decrypt_subject_to_recipient(recipient)
+}
+
+/// Decrypt the envelope's subject as whichever of the `recipients` it was
+/// encrypted to, returning the decrypted envelope and the index of the
+/// recipient whose key matched.
+pub fn decrypt_to_any_recipient(envelope: &Envelope, recipients: &[&dyn Decrypter]) -> Result<(Envelope, usize)> {
+    if recipients.is_empty() {
+        bail!("at least one recipient must be provided");
+    }
+    let mut errors = Vec::new();
+    for (index, recipient) in recipients.iter().enumerate() {
+        match envelope.decrypt_subject_to_recipient(*recipient) {
+            Ok(decrypted) => return Ok((decrypted, index)),
+            Err(error) => errors.push(error),
+        }
+    }
+    match errors.pop() {
+        Some(error) if recipients.len() == 1 => Err(error),
+        _ => bail!("none of the recipients can decrypt the envelope"),
+    }
+}
+
+/// The content key of the envelope's encrypted subject, recovered from the
+/// `hasRecipient` assertion sealed to `recipient`.
+pub fn content_key(envelope: &Envelope, recipient: &dyn Decrypter) -> Result<SymmetricKey> {
+    for sealed_message in envelope.recipients()? {
+        if let Ok(data) = sealed_message.decrypt(recipient) {
+            return SymmetricKey::from_tagged_cbor_data(data);
+        }
+    }
+    bail!(EnvelopeError::UnknownRecipient)
+}
+
+/// Add a `hasRecipient` assertion sealing `content_key` to `recipient`,
+/// leaving the encrypted subject unchanged. Fails if `content_key` does not
+/// decrypt the subject.
+pub fn add_recipient(envelope: &Envelope, content_key: &SymmetricKey, recipient: &PublicKeys) -> Result<Envelope> {
+    if !envelope.subject().is_encrypted() {
+        bail!("envelope subject is not encrypted");
+    }
+    if envelope.decrypt_subject(content_key).is_err() {
+        bail!("the content key does not decrypt the envelope subject");
+    }
+    Ok(envelope.add_recipient(recipient, content_key))
+}
+
+/// Remove the `hasRecipient` assertions that `recipient` can open, leaving
+/// the encrypted subject unchanged.
+pub fn remove_recipient(envelope: &Envelope, recipient: &dyn Decrypter) -> Result<Envelope> {
+    let mut result = envelope.clone();
+    for assertion in envelope.assertions_with_predicate(HAS_RECIPIENT) {
+        let sealed_message: SealedMessage = assertion.try_object()?.extract_subject()?;
+        if sealed_message.decrypt(recipient).is_ok() {
+            result = result.remove_assertion(assertion);
+        }
+    }
+    if result.digest() == envelope.digest() {
+        bail!(EnvelopeError::UnknownRecipient);
+    }
+    Ok(result)
+}
+
+/// Remove the `hasRecipient` assertion with the given digest, leaving the
+/// encrypted subject unchanged.
+pub fn remove_recipient_assertion(envelope: &Envelope, digest: &Digest) -> Result<Envelope> {
+    match envelope.assertions_with_predicate(HAS_RECIPIENT).into_iter().find(|assertion| assertion.digest().as_ref() == digest) {
+        Some(assertion) => Ok(envelope.remove_assertion(assertion)),
+        None => bail!("envelope has no hasRecipient assertion with this digest"),
+    }
+}
+
+/// What can be learned about an envelope's encrypted subject without a key.
+pub struct EncryptionInfo {
+    /// The encapsulation scheme of each `hasRecipient` sealed message, or
+    /// `None` where the sealed message is obscured.
+    pub recipients: Vec<Option<EncapsulationScheme>>,
+    pub nonce_size: usize,
+    /// Whether the encrypted subject carries the digest of its plaintext.
+    pub has_digest: bool,
+    /// The function the key was derived with, if encrypted with a password.
+    pub kdf: Option<String>,
+}
+
+/// Describe the envelope's encrypted subject, or return `None` if the
+/// subject is not encrypted.
+pub fn encryption_info(envelope: &Envelope) -> Result<Option<EncryptionInfo>> {
+    let subject = envelope.subject();
+    let EnvelopeCase::Encrypted(message) = subject.case() else {
+        return Ok(None);
+    };
+    let mut recipients = Vec::new();
+    for assertion in envelope.assertions_with_predicate(HAS_RECIPIENT) {
+        let object = assertion.try_object()?;
+        recipients.push(if object.is_obscured() {
+            None
+        } else {
+            Some(object.extract_subject::<SealedMessage>()?.encapsulation_scheme())
+        });
+    }
+    let kdf = match envelope.optional_object_for_predicate(KDF)? {
+        Some(kdf) => Some(kdf.extract_subject()?),
+        None => None,
+    };
+    Ok(Some(EncryptionInfo {
+        recipients,
+        nonce_size: message.nonce().data().len(),
+        has_digest: message.has_digest(),
+        kdf,
+    }))
+}
+
+/// Derive a new key from `password` with a random salt. Returns the key and
+/// the assertion recording the salt and parameters needed to derive it again.
+pub fn new_password_key(password: &str) -> Result<(SymmetricKey, Envelope)> {
+    let salt = Salt::new_with_len(SALT_LENGTH)?;
+    let params = Params::default();
+    let key = derive_key(password, &salt, &params)?;
+    let kdf = Envelope::new(ARGON2ID)
+        .add_assertion(SALT, salt)
+        .add_assertion(MEMORY, params.m_cost())
+        .add_assertion(ITERATIONS, params.t_cost())
+        .add_assertion(PARALLELISM, params.p_cost());
+    Ok((key, Envelope::new_assertion(KDF, kdf)))
+}
+
+/// Derive the key from `password` with the salt and parameters recorded on
+/// the envelope.
+pub fn password_key(envelope: &Envelope, password: &str) -> Result<SymmetricKey> {
+    let kdf = envelope.optional_object_for_predicate(KDF)?
+        .ok_or_else(|| anyhow!("envelope is not encrypted with a password"))?;
+    kdf_key(&kdf, password)
+}
+
+/// Derive the key from `password` with the salt and parameters of `kdf`, the
+/// object of a `"kdf"` assertion.
+fn kdf_key(kdf: &Envelope, password: &str) -> Result<SymmetricKey> {
+    let algorithm: String = kdf.extract_subject()?;
+    if algorithm != ARGON2ID {
+        bail!("unsupported key derivation function: {}", algorithm);
+    }
+    let salt: Salt = kdf.extract_object_for_predicate(SALT)?;
+    let memory: u32 = kdf.extract_object_for_predicate(MEMORY)?;
+    let iterations: u32 = kdf.extract_object_for_predicate(ITERATIONS)?;
+    let parallelism: u32 = kdf.extract_object_for_predicate(PARALLELISM)?;
+    if memory > MAX_MEMORY {
+        bail!("key derivation memory cost {} KiB exceeds the maximum of {} KiB", memory, MAX_MEMORY);
+    }
+    if iterations > MAX_ITERATIONS {
+        bail!("key derivation iterations {} exceed the maximum of {}", iterations, MAX_ITERATIONS);
+    }
+    if parallelism > MAX_PARALLELISM {
+        bail!("key derivation parallelism {} exceeds the maximum of {}", parallelism, MAX_PARALLELISM);
+    }
+    let params = Params::new(memory, iterations, parallelism, None)
+        .map_err(|e| anyhow!("invalid key derivation parameters: {}", e))?;
+    derive_key(password, &salt, &params)
+}
+
+fn derive_key(password: &str, salt: &Salt, params: &Params) -> Result<SymmetricKey> {
+    let mut key = [0u8; SymmetricKey::SYMMETRIC_KEY_SIZE];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+        .hash_password_into(password.as_bytes(), salt.data(), &mut key)
+        .map_err(|e| anyhow!("key derivation failed: {}", e))?;
+    Ok(SymmetricKey::from_data(key))
+}
+
+/// Encrypt the envelope's subject with a key derived from `password`.
+pub fn encrypt_with_password(envelope: &Envelope, password: &str) -> Result<Envelope> {
+    let (key, kdf) = new_password_key(password)?;
+    envelope.encrypt_subject(&key)?.add_assertion_envelope(kdf)
+}
+
+/// Add the `kdf` assertion, as returned by `new_password_key`, to each
+/// element of the envelope that is encrypted with `key`, so that the element
+/// can be decrypted with the password wherever the envelope is later wrapped
+/// or extended.
+pub fn add_kdf_assertions(envelope: &Envelope, key: &SymmetricKey, kdf: &Envelope) -> Result<Envelope> {
+    Ok(match envelope.case() {
+        EnvelopeCase::Encrypted(_) if envelope.decrypt_subject(key).is_ok() => envelope.add_assertion_envelope(kdf)?,
+        EnvelopeCase::Node { subject, assertions, .. } => {
+            let subject = add_kdf_assertions(subject, key, kdf)?;
+            let mut marked_assertions = Vec::new();
+            for assertion in assertions {
+                marked_assertions.push(add_kdf_assertions(assertion, key, kdf)?);
+            }
+            subject.add_assertion_envelopes(&marked_assertions)?
+        }
+        EnvelopeCase::Wrapped { envelope: content, .. } => add_kdf_assertions(content, key, kdf)?.wrap_envelope(),
+        EnvelopeCase::Assertion(assertion) => Envelope::new_assertion(
+            add_kdf_assertions(&assertion.predicate(), key, kdf)?,
+            add_kdf_assertions(&assertion.object(), key, kdf)?,
+        ),
+        _ => envelope.clone(),
+    })
+}
+
+/// Decrypt every element of the envelope that carries a `"kdf"` assertion,
+/// and any elements within it, with the key derived from `password`, and
+/// remove those assertions.
+pub fn decrypt_with_password(envelope: &Envelope, password: &str) -> Result<Envelope> {
+    let mut keys = HashMap::new();
+    let mut decrypted = false;
+    let result = decrypt_elements(envelope, password, None, &mut keys, &mut decrypted)?;
+    if !decrypted {
+        bail!("could not decrypt the envelope with the password");
+    }
+    Ok(result)
+}
+
+/// Decrypt each element encrypted with `key`, or with the key derived from
+/// the `"kdf"` assertion of an enclosing node, setting `decrypted` if any
+/// was. Derived keys are cached in `keys` by the digest of their `"kdf"`.
+fn decrypt_elements(
+    envelope: &Envelope,
+    password: &str,
+    key: Option<&SymmetricKey>,
+    keys: &mut HashMap<Digest, SymmetricKey>,
+    decrypted: &mut bool,
+) -> Result<Envelope> {
+    Ok(match envelope.case() {
+        EnvelopeCase::Encrypted(_) => match key.map(|key| envelope.decrypt_subject(key)) {
+            Some(Ok(content)) => {
+                *decrypted = true;
+                decrypt_elements(&content, password, key, keys, decrypted)?
+            }
+            _ => envelope.clone(),
+        },
+        EnvelopeCase::Node { subject, assertions, .. } => {
+            let (key, assertions) = match envelope.optional_assertion_with_predicate(KDF)? {
+                Some(kdf_assertion) => {
+                    let kdf = kdf_assertion.try_object()?;
+                    let key = match keys.get(&kdf.digest()) {
+                        Some(key) => key.clone(),
+                        None => {
+                            let key = kdf_key(&kdf, password)?;
+                            keys.insert(kdf.digest().into_owned(), key.clone());
+                            key
+                        }
+                    };
+                    let assertions = assertions.iter()
+                        .filter(|assertion| assertion.digest() != kdf_assertion.digest())
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (Some(key), assertions)
+                }
+                None => (key.cloned(), assertions.clone()),
+            };
+            let subject = decrypt_elements(subject, password, key.as_ref(), keys, decrypted)?;
+            let mut decrypted_assertions = Vec::new();
+            for assertion in &assertions {
+                decrypted_assertions.push(decrypt_elements(assertion, password, key.as_ref(), keys, decrypted)?);
+            }
+            subject.add_assertion_envelopes(&decrypted_assertions)?
+        }
+        EnvelopeCase::Wrapped { envelope: content, .. } => decrypt_elements(content, password, key, keys, decrypted)?.wrap_envelope(),
+        EnvelopeCase::Assertion(assertion) => Envelope::new_assertion(
+            decrypt_elements(&assertion.predicate(), password, key, keys, decrypted)?,
+            decrypt_elements(&assertion.object(), password, key, keys, decrypted)?,
+        ),
+        _ => envelope.clone(),
+    })
+}
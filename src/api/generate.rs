@@ -1,8 +1,23 @@
-use bc_components::{EncapsulationPublicKey, PrivateKeyBase, PublicKeys, SigningPrivateKey};
+//! Generating seeds and deriving keys (`envelope generate`).
+
+use bc_components::{EncapsulationPublicKey, PrivateKeyBase, PublicKeys, Seed, SigningPrivateKey};
 use clap::ValueEnum;
 use ssh_key::{Algorithm as SSHAlgorithm, EcdsaCurve, HashAlg};
 use anyhow::{Result, bail};
 
+/// Generate a random seed of `count` bytes, which must be in the range
+/// 16..=256.
+pub fn seed(count: usize) -> Result<Seed> {
+    if count < Seed::MIN_SEED_LENGTH {
+        bail!("Seed length must be at least 16 bytes");
+    }
+    if count > 256 {
+        bail!("Seed length must be at most 256 bytes");
+    }
+    Seed::new_with_len(count)
+}
+
+/// The kinds of signing keys that can be derived from a private key base.
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SignerType {
     /// Schnorr
@@ -49,6 +64,7 @@ pub enum SignerType {
 }
 
 impl SignerType {
+    /// Derive a signing private key of this type from a private key base.
     pub fn to_signing_private_key(self, private_key_base: &PrivateKeyBase, ssh_comment: impl Into<String>) -> Result<SigningPrivateKey> {
         match self {
             Self::Schnorr => Ok(private_key_base.schnorr_signing_private_key()),
@@ -69,6 +85,8 @@ impl SignerType {
         }
     }
 
+    /// Derive the `PublicKeys` of a private key base, using a signing key of
+    /// this type.
     pub fn to_public_keys(self, private_key_base: &PrivateKeyBase, ssh_comment: impl Into<String>) -> Result<PublicKeys> {
         let signing_private_key = self.to_signing_private_key(private_key_base, ssh_comment)?;
         Ok(PublicKeys::new(
@@ -0,0 +1,24 @@
+//! Typed functions implementing the `envelope` commands.
+//!
+//! Each function takes and returns envelopes, keys, and XID documents rather
+//! than UR strings, so the behavior of the command line tool can be used from
+//! Rust directly. The `envelope` binary parses its arguments, calls these
+//! functions, and formats their results.
+//!
+//! Commands that only inspect or convert an envelope, such as `format`,
+//! `digest`, `extract`, `import`, and `export`, correspond directly to methods
+//! of [`bc_envelope::Envelope`] and have no counterpart here.
+
+pub mod assertion;
+pub mod compress;
+pub mod diff;
+pub mod elide;
+pub mod encrypt;
+pub mod generate;
+pub mod patch;
+pub mod proof;
+pub mod query;
+pub mod salt;
+pub mod sign;
+pub mod sskr;
+pub mod xid;
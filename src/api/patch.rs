@@ -0,0 +1,216 @@
+//! Patches that transform one envelope into another (`envelope patch`).
+//!
+//! A patch is an envelope whose subject is the digest of the base envelope it
+//! applies to:
+//!
+//! ```text
+//! Digest(base) [
+//!     "result": Digest(patched)
+//!     "remove": Digest(assertion) [
+//!         "path": [Digest, ...]
+//!     ]
+//!     "add": {
+//!         assertion
+//!     } [
+//!         "path": [Digest, ...]
+//!     ]
+//! ]
+//! ```
+//!
+//! Each operation removes or adds an assertion on the element reached by its
+//! `path`, the digests of the elements of the base envelope leading from the
+//! root to the target. Operations on the root have no `path`.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Result};
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*};
+
+const RESULT: &str = "result";
+const ADD: &str = "add";
+const REMOVE: &str = "remove";
+const PATH: &str = "path";
+
+enum Operation {
+    Add(Envelope),
+    Remove(Digest),
+}
+
+struct Change {
+    path: Vec<Digest>,
+    operation: Operation,
+}
+
+/// Create a patch that transforms `old` into `new`.
+///
+/// Changes to an assertion's object are expressed as changes within the
+/// object where possible, and otherwise as the removal of the old assertion
+/// and the addition of the new one. Fails if the envelopes differ in a way
+/// that adding and removing assertions cannot express, such as a different
+/// subject.
+pub fn create(old: &Envelope, new: &Envelope) -> Result<Envelope> {
+    let changes = match diff_element(old, new, &[]) {
+        Some(changes) => changes,
+        None => bail!("envelopes with different subjects cannot be patched"),
+    };
+    let patch = changes.into_iter().fold(
+        Envelope::new(old.digest().into_owned()).add_assertion(RESULT, new.digest().into_owned()),
+        |patch, change| {
+            let (predicate, object) = match change.operation {
+                Operation::Add(assertion) => (ADD, assertion.wrap_envelope()),
+                Operation::Remove(digest) => (REMOVE, Envelope::new(digest)),
+            };
+            let object = if change.path.is_empty() {
+                object
+            } else {
+                object.add_assertion(PATH, path_to_cbor(&change.path))
+            };
+            patch.add_assertion(predicate, object)
+        },
+    );
+    Ok(patch)
+}
+
+/// Apply a patch to its base envelope.
+///
+/// If the patch is wrapped, for example because it is signed, the wrapped
+/// patch is applied. Fails if `base` is not the envelope the patch was
+/// created from or the result does not have the expected digest.
+pub fn apply(base: &Envelope, patch: &Envelope) -> Result<Envelope> {
+    let patch = if patch.subject().is_wrapped() { patch.subject().unwrap_envelope()? } else { patch.clone() };
+    let base_digest: Digest = patch.extract_subject()?;
+    if base_digest != *base.digest() {
+        bail!("patch base digest does not match the envelope");
+    }
+    let result_digest: Digest = patch.extract_object_for_predicate(RESULT)?;
+    let mut changes = Vec::new();
+    for object in patch.objects_for_predicate(ADD) {
+        changes.push(Change { path: object_path(&object)?, operation: Operation::Add(object.subject().unwrap_envelope()?) });
+    }
+    for object in patch.objects_for_predicate(REMOVE) {
+        changes.push(Change { path: object_path(&object)?, operation: Operation::Remove(object.extract_subject()?) });
+    }
+    let result = apply_changes(base, changes.iter().map(|change| (change.path.as_slice(), change)).collect())?;
+    if result_digest != *result.digest() {
+        bail!("patched envelope does not match the patch result digest");
+    }
+    Ok(result)
+}
+
+/// The changes that transform `old` into `new`, which is at `path`, or `None`
+/// if they differ in more than their assertions.
+fn diff_element(old: &Envelope, new: &Envelope, path: &[Digest]) -> Option<Vec<Change>> {
+    if old.digest() == new.digest() {
+        return Some(Vec::new());
+    }
+    if old.is_obscured() || new.is_obscured() {
+        return None;
+    }
+    let mut changes = Vec::new();
+    let (old_subject, new_subject) = (old.subject(), new.subject());
+    if old_subject.digest() != new_subject.digest() {
+        let (EnvelopeCase::Wrapped { envelope: old_content, .. }, EnvelopeCase::Wrapped { envelope: new_content, .. }) = (old_subject.case(), new_subject.case()) else {
+            return None;
+        };
+        let mut content_path = path.to_vec();
+        if !old.assertions().is_empty() {
+            content_path.push(old_subject.digest().into_owned());
+        }
+        content_path.push(old_content.digest().into_owned());
+        changes.extend(diff_element(old_content, new_content, &content_path)?);
+    }
+
+    let (old_assertions, new_assertions) = (old.assertions(), new.assertions());
+    let old_digests = old_assertions.iter().map(|assertion| assertion.digest().into_owned()).collect::<HashSet<_>>();
+    let new_digests = new_assertions.iter().map(|assertion| assertion.digest().into_owned()).collect::<HashSet<_>>();
+    let mut added = new_assertions.iter().filter(|assertion| !old_digests.contains(&assertion.digest())).collect::<Vec<_>>();
+    for old_assertion in old_assertions.iter().filter(|assertion| !new_digests.contains(&assertion.digest())) {
+        // An assertion that is the only one with its predicate in both
+        // envelopes is patched within its object where possible.
+        let same_predicate = |assertion: &&Envelope| match (assertion.as_predicate(), old_assertion.as_predicate()) {
+            (Some(predicate), Some(old_predicate)) => predicate.digest() == old_predicate.digest(),
+            _ => false,
+        };
+        let object_changes = match added.iter().position(same_predicate) {
+            Some(index) if old_assertions.iter().filter(same_predicate).count() == 1
+                && new_assertions.iter().filter(same_predicate).count() == 1 =>
+            {
+                let old_object = old_assertion.as_object().unwrap();
+                let mut object_path = path.to_vec();
+                object_path.push(old_assertion.digest().into_owned());
+                object_path.push(old_object.digest().into_owned());
+                diff_element(&old_object, &added[index].as_object().unwrap(), &object_path)
+                    .map(|object_changes| (index, object_changes))
+            }
+            _ => None,
+        };
+        match object_changes {
+            Some((index, object_changes)) => {
+                added.remove(index);
+                changes.extend(object_changes);
+            }
+            None => changes.push(Change { path: path.to_vec(), operation: Operation::Remove(old_assertion.digest().into_owned()) }),
+        }
+    }
+    for assertion in added {
+        changes.push(Change { path: path.to_vec(), operation: Operation::Add(assertion.clone()) });
+    }
+    Some(changes)
+}
+
+/// Apply the changes to `envelope`, with each change paired with the rest of
+/// its path below `envelope`.
+fn apply_changes(envelope: &Envelope, changes: Vec<(&[Digest], &Change)>) -> Result<Envelope> {
+    let mut removals = HashSet::new();
+    let mut additions = Vec::new();
+    let mut nested: HashMap<Digest, Vec<(&[Digest], &Change)>> = HashMap::new();
+    for (path, change) in changes {
+        match (path.split_first(), &change.operation) {
+            (Some((digest, rest)), _) => nested.entry(digest.clone()).or_default().push((rest, change)),
+            (None, Operation::Add(assertion)) => additions.push(assertion.clone()),
+            (None, Operation::Remove(digest)) => { removals.insert(digest.clone()); }
+        }
+    }
+    let mut apply_child = |child: &Envelope| match nested.remove(&child.digest()) {
+        Some(changes) => apply_changes(child, changes),
+        None => Ok(child.clone()),
+    };
+
+    let result = match envelope.case() {
+        EnvelopeCase::Node { subject, assertions, .. } => {
+            let subject = apply_child(subject)?;
+            let mut kept = Vec::new();
+            for assertion in assertions {
+                if !removals.remove(&assertion.digest()) {
+                    kept.push(apply_child(assertion)?);
+                }
+            }
+            subject.add_assertion_envelopes(&kept)?
+        }
+        EnvelopeCase::Wrapped { envelope: content, .. } => apply_child(content)?.wrap_envelope(),
+        EnvelopeCase::Assertion(assertion) => {
+            let object = apply_child(&assertion.object())?;
+            let predicate = apply_child(&assertion.predicate())?;
+            Envelope::new_assertion(predicate, object)
+        }
+        _ => envelope.clone(),
+    };
+    if !nested.is_empty() {
+        bail!("patch path not found in the envelope");
+    }
+    if !removals.is_empty() {
+        bail!("assertion to remove not found in the envelope");
+    }
+    result.add_assertion_envelopes(&additions)
+}
+
+fn path_to_cbor(path: &[Digest]) -> CBOR {
+    path.iter().map(|digest| CBOR::from(digest.clone())).collect::<Vec<_>>().into()
+}
+
+fn object_path(object: &Envelope) -> Result<Vec<Digest>> {
+    match object.optional_object_for_predicate(PATH)? {
+        Some(path) => path.try_leaf()?.try_into_array()?.into_iter().map(Digest::try_from).collect(),
+        None => Ok(Vec::new()),
+    }
+}
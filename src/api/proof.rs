@@ -0,0 +1,32 @@
+//! Inclusion proofs (`envelope proof`).
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use bc_envelope::prelude::*;
+
+/// Create a proof that the envelope contains every digest in `target`.
+pub fn create(envelope: &Envelope, target: &HashSet<Digest>) -> Result<Envelope> {
+    match envelope.proof_contains_set(target) {
+        Some(proof) => Ok(proof),
+        None => bail!("No proof found for target set"),
+    }
+}
+
+/// Confirm, using `proof`, that the envelope contains every digest in
+/// `target`.
+pub fn confirm(envelope: &Envelope, proof: &Envelope, target: &HashSet<Digest>) -> Result<()> {
+    if !envelope.clone().confirm_contains_set(target, proof) {
+        bail!("Proof does not confirm target");
+    }
+    Ok(())
+}
+
+/// Confirm, using `proof`, that the envelope with the trusted digest `root`
+/// contains every digest in `target`, without needing the envelope itself.
+pub fn confirm_digest(root: &Digest, proof: &Envelope, target: &HashSet<Digest>) -> Result<()> {
+    if *proof.digest() != *root || !proof.confirm_contains_set(target, proof) {
+        bail!("Proof does not confirm target");
+    }
+    Ok(())
+}
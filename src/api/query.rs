@@ -0,0 +1,302 @@
+//! Selecting nodes of an envelope with a path query (`envelope query`).
+//!
+//! A query is a sequence of steps applied to a set of nodes, starting with
+//! the envelope itself:
+//!
+//! - `.pred` selects the objects of the assertions with predicate `pred`.
+//! - `..pred` does the same for the node and every node nested within it.
+//! - `{}` selects the envelope wrapped in the subject.
+//! - `[*]` keeps every node, `[pred]` keeps the nodes having an assertion with
+//!   predicate `pred`, `[pred=value]` keeps those where the object of that
+//!   assertion is `value`, and `[=value]` keeps the nodes whose subject is
+//!   `value`.
+//!
+//! Predicates and values are terms: `*` matches anything, `"text"` matches a
+//! string, `'name'` matches a known value by name or number, and a bare word
+//! matches a string, named known value, or number with that text.
+
+use anyhow::{bail, Result};
+use bc_envelope::prelude::*;
+
+/// Return the nodes of the envelope selected by the query, in order and
+/// without duplicates.
+pub fn query(envelope: &Envelope, query: &str) -> Result<Vec<Envelope>> {
+    let steps = parse(query)?;
+    let mut nodes = vec![envelope.clone()];
+    for step in &steps {
+        let mut selected: Vec<Envelope> = Vec::new();
+        for node in &nodes {
+            for envelope in step.apply(node) {
+                if !selected.iter().any(|e| e.digest() == envelope.digest()) {
+                    selected.push(envelope);
+                }
+            }
+        }
+        nodes = selected;
+    }
+    Ok(nodes)
+}
+
+/// Return the assertions anywhere in the envelope whose predicate matches the
+/// term, including within wrapped envelopes.
+pub fn find_assertions(envelope: &Envelope, predicate: &str) -> Result<Vec<Envelope>> {
+    let predicate = parse_term(predicate)?;
+    let mut nodes = Vec::new();
+    descendants(envelope, &mut nodes);
+    Ok(nodes.iter()
+        .flat_map(|node| node.assertions())
+        .filter(|assertion| assertion.as_predicate().is_some_and(|p| predicate.matches(&p)))
+        .collect())
+}
+
+/// Return the objects anywhere in the envelope that match the term, including
+/// within wrapped envelopes.
+pub fn find_objects(envelope: &Envelope, value: &str) -> Result<Vec<Envelope>> {
+    let value = parse_term(value)?;
+    let mut nodes = Vec::new();
+    descendants(envelope, &mut nodes);
+    Ok(nodes.iter()
+        .flat_map(|node| objects(node, &Term::Any))
+        .filter(|object| !object.subject().is_obscured() && value.matches(object))
+        .collect())
+}
+
+enum Term {
+    Any,
+    Text(String),
+    Known(KnownValue),
+    Bare { text: String, known: Option<KnownValue>, number: Option<f64> },
+}
+
+enum Filter {
+    Any,
+    Predicate(Term),
+    Assertion(Term, Term),
+    Subject(Term),
+}
+
+enum Step {
+    Objects(Term),
+    Descendants(Term),
+    Unwrap,
+    Filter(Filter),
+}
+
+impl Term {
+    fn matches(&self, envelope: &Envelope) -> bool {
+        let subject = envelope.subject();
+        let text = || subject.extract_subject::<String>().ok();
+        let known_value = || subject.as_known_value().map(|known_value| known_value.value());
+        match self {
+            Term::Any => true,
+            Term::Text(s) => text().as_ref() == Some(s),
+            Term::Known(k) => known_value() == Some(k.value()),
+            Term::Bare { text: s, known, number } => {
+                text().as_ref() == Some(s)
+                    || known.as_ref().is_some_and(|k| known_value() == Some(k.value()))
+                    || number.is_some_and(|n| !subject.is_known_value() && subject.extract_subject::<f64>().ok() == Some(n))
+            }
+        }
+    }
+}
+
+impl Filter {
+    fn matches(&self, node: &Envelope) -> bool {
+        match self {
+            Filter::Any => true,
+            Filter::Predicate(predicate) => !objects(node, predicate).is_empty(),
+            Filter::Assertion(predicate, value) => objects(node, predicate).iter().any(|object| value.matches(object)),
+            Filter::Subject(value) => !node.subject().is_obscured() && value.matches(node),
+        }
+    }
+}
+
+impl Step {
+    fn apply(&self, node: &Envelope) -> Vec<Envelope> {
+        match self {
+            Step::Objects(predicate) => objects(node, predicate),
+            Step::Descendants(predicate) => {
+                let mut nodes = Vec::new();
+                descendants(node, &mut nodes);
+                nodes.iter().flat_map(|node| objects(node, predicate)).collect()
+            }
+            Step::Unwrap => node.subject().unwrap_envelope().into_iter().collect(),
+            Step::Filter(filter) => if filter.matches(node) { vec![node.clone()] } else { vec![] },
+        }
+    }
+}
+
+/// The objects of the node's assertions whose predicates match.
+fn objects(node: &Envelope, predicate: &Term) -> Vec<Envelope> {
+    node.assertions().iter()
+        .filter(|assertion| assertion.as_predicate().is_some_and(|p| predicate.matches(&p)))
+        .filter_map(|assertion| assertion.as_object())
+        .collect()
+}
+
+/// The node and every node nested in its wrapped subject or its objects.
+fn descendants(node: &Envelope, nodes: &mut Vec<Envelope>) {
+    nodes.push(node.clone());
+    if let Ok(content) = node.subject().unwrap_envelope() {
+        descendants(&content, nodes);
+    }
+    for assertion in node.assertions() {
+        if let Some(object) = assertion.as_object() {
+            descendants(&object, nodes);
+        }
+    }
+}
+
+struct Parser<'a> {
+    query: &'a str,
+    position: usize,
+}
+
+fn parse(query: &str) -> Result<Vec<Step>> {
+    let mut parser = Parser { query, position: 0 };
+    let mut steps = Vec::new();
+    parser.skip_whitespace();
+    while let Some(c) = parser.peek() {
+        let step = match c {
+            '.' => {
+                parser.next();
+                if parser.peek() == Some('.') {
+                    parser.next();
+                    Step::Descendants(parser.term(false)?)
+                } else {
+                    Step::Objects(parser.term(false)?)
+                }
+            }
+            '{' => {
+                parser.next();
+                parser.expect('}')?;
+                Step::Unwrap
+            }
+            '[' => {
+                parser.next();
+                let filter = parser.filter()?;
+                parser.expect(']')?;
+                Step::Filter(filter)
+            }
+            _ => bail!("unexpected '{}' at position {} in query", c, parser.position),
+        };
+        steps.push(step);
+        parser.skip_whitespace();
+    }
+    Ok(steps)
+}
+
+fn parse_term(term: &str) -> Result<Term> {
+    let mut parser = Parser { query: term, position: 0 };
+    let result = parser.term(true)?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        bail!("unexpected text at position {} in '{}'", parser.position, term);
+    }
+    Ok(result)
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.query[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => bail!("expected '{}' at position {} in query", expected, self.position),
+        }
+    }
+
+    fn filter(&mut self) -> Result<Filter> {
+        self.skip_whitespace();
+        if self.peek() == Some('=') {
+            self.next();
+            return Ok(Filter::Subject(self.term(true)?));
+        }
+        let predicate = self.term(true)?;
+        self.skip_whitespace();
+        if self.peek() == Some('=') {
+            self.next();
+            Ok(Filter::Assertion(predicate, self.term(true)?))
+        } else if let Term::Any = predicate {
+            Ok(Filter::Any)
+        } else {
+            Ok(Filter::Predicate(predicate))
+        }
+    }
+
+    /// Parse a term. Bare words may contain `.` only within a filter, so that
+    /// numbers like `1.5` can be matched.
+    fn term(&mut self, in_filter: bool) -> Result<Term> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('*') => {
+                self.next();
+                Ok(Term::Any)
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.next();
+                let mut text = String::new();
+                loop {
+                    match self.next() {
+                        Some('\\') => match self.next() {
+                            Some(c) => text.push(c),
+                            None => bail!("unterminated quote in query"),
+                        },
+                        Some(c) if c == quote => break,
+                        Some(c) => text.push(c),
+                        None => bail!("unterminated quote in query"),
+                    }
+                }
+                if quote == '"' {
+                    Ok(Term::Text(text))
+                } else {
+                    match known_value(&text) {
+                        Some(known_value) => Ok(Term::Known(known_value)),
+                        None => bail!("unknown known value '{}' in query", text),
+                    }
+                }
+            }
+            _ => {
+                let start = self.position;
+                while let Some(c) = self.peek() {
+                    if c.is_whitespace() || "[]{}=\"'*".contains(c) || (c == '.' && !in_filter) {
+                        break;
+                    }
+                    self.next();
+                }
+                let text = &self.query[start..self.position];
+                if text.is_empty() {
+                    bail!("expected a predicate or value at position {} in query", start);
+                }
+                let number = text.parse().ok();
+                let known = if number.is_some() { None } else { known_value(text) };
+                Ok(Term::Bare { text: text.to_string(), known, number })
+            }
+        }
+    }
+}
+
+/// The known value with the given name or number.
+fn known_value(s: &str) -> Option<KnownValue> {
+    if let Ok(number) = s.parse::<u64>() {
+        return Some(KnownValue::new(number));
+    }
+    with_format_context!(|context: &FormatContext| {
+        KnownValuesStore::known_value_for_name(s, Some(context.known_values()))
+    })
+}
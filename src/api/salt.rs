@@ -0,0 +1,13 @@
+//! Salting envelopes (`envelope salt`).
+
+use anyhow::Result;
+use bc_envelope::prelude::*;
+
+/// Add salt of `size` bytes to the envelope, or of a random size
+/// proportional to the envelope's size if `size` is `None`.
+pub fn salt(envelope: &Envelope, size: Option<usize>) -> Result<Envelope> {
+    match size {
+        Some(size) => envelope.add_salt_with_len(size),
+        None => Ok(envelope.add_salt()),
+    }
+}
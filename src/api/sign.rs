@@ -0,0 +1,92 @@
+//! Signing and verifying envelopes (`envelope sign` and `envelope verify`).
+//!
+//! There are no registered known values for a signer or role, so signature
+//! metadata records them under the string predicates [`SIGNER`] and [`ROLE`].
+//! These are part of the signature format and will continue to be read as
+//! they are.
+
+use anyhow::{bail, Result};
+use bc_components::{Digest, Signer, SigningOptions, Verifier};
+use bc_envelope::prelude::*;
+use known_values::NOTE;
+
+/// The predicate under which a signer's XID is recorded in signature
+/// metadata.
+pub const SIGNER: &str = "signer";
+
+/// The predicate under which the purpose or role of a signature, a known
+/// value, is recorded in signature metadata.
+pub const ROLE: &str = "role";
+
+/// Sign the envelope's subject with each of the `signers`, using the given
+/// signing options (required for SSH keys).
+///
+/// A `note` may only be added when there is a single signer, and is recorded
+/// in the signature's metadata.
+pub fn sign(envelope: &Envelope, signers: &[(&dyn Signer, Option<SigningOptions>)], note: Option<&str>) -> Result<Envelope> {
+    if note.is_some() && signers.len() != 1 {
+        bail!("can only add a note on a single signature");
+    }
+    let metadata = note.map(|note| SignatureMetadata::new().with_assertion(NOTE, note));
+    let signers = signers.iter()
+        .map(|(signer, options)| (*signer, options.clone(), metadata.clone()))
+        .collect::<Vec<_>>();
+    sign_with_metadata(envelope, &signers)
+}
+
+/// Sign the envelope's subject with each of the `signers`, recording each
+/// signer's metadata, if any, in its signature.
+pub fn sign_with_metadata(envelope: &Envelope, signers: &[(&dyn Signer, Option<SigningOptions>, Option<SignatureMetadata>)]) -> Result<Envelope> {
+    if signers.is_empty() {
+        bail!("at least one signer must be provided");
+    }
+    Ok(envelope.add_signatures_opt(signers))
+}
+
+/// Create a detached signature for `digest`, an envelope whose subject is the
+/// digest and which has a `'signed'` assertion from each of the `signers`.
+///
+/// The signatures are the same as those `sign_with_metadata` attaches to an
+/// envelope whose subject has this digest.
+pub fn sign_detached(digest: &Digest, signers: &[(&dyn Signer, Option<SigningOptions>, Option<SignatureMetadata>)]) -> Result<Envelope> {
+    let signed = sign_with_metadata(&elided_with_digest(digest)?, signers)?;
+    Envelope::new(digest.clone()).add_assertion_envelopes(&signed.assertions())
+}
+
+/// The envelope to verify a detached signature of `digest` against: an elided
+/// envelope with that digest carrying the signature's assertions.
+pub fn detached_target(digest: &Digest, signature: &Envelope) -> Result<Envelope> {
+    let signed_digest: Digest = signature.extract_subject()?;
+    if signed_digest != *digest {
+        bail!("detached signature is for a different digest");
+    }
+    elided_with_digest(digest)?.add_assertion_envelopes(&signature.assertions())
+}
+
+/// An elided envelope with the given digest.
+fn elided_with_digest(digest: &Digest) -> Result<Envelope> {
+    Envelope::from_untagged_cbor(CBOR::to_byte_string(digest.data()))
+}
+
+/// Verify that the envelope's subject has at least `threshold` valid
+/// signatures from the `verifiers`.
+pub fn verify(envelope: &Envelope, verifiers: &[&dyn Verifier], threshold: usize) -> Result<()> {
+    if verifiers.is_empty() {
+        bail!("at least one verifier must be provided");
+    }
+    envelope.clone().verify_signatures_from_threshold(verifiers, Some(threshold))?;
+    Ok(())
+}
+
+/// The metadata of each of the envelope's signatures that is valid for one of
+/// the `verifiers`, in the order of the verifiers. A signature without
+/// metadata is returned as the bare `Signature`.
+pub fn verified_metadata(envelope: &Envelope, verifiers: &[&dyn Verifier]) -> Result<Vec<Envelope>> {
+    let mut metadata = Vec::new();
+    for verifier in verifiers {
+        if let Some(envelope) = envelope.has_signature_from_returning_metadata(*verifier)? {
+            metadata.push(envelope);
+        }
+    }
+    Ok(metadata)
+}
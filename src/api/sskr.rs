@@ -0,0 +1,82 @@
+//! Splitting envelopes into SSKR shares and joining them (`envelope sskr`).
+
+use anyhow::{bail, Result};
+use bc_components::{PublicKeys, SSKRSpec, SymmetricKey};
+use bc_envelope::prelude::*;
+
+/// The recipient of particular shares, such as a trustee in a social recovery
+/// scheme.
+pub struct ShareRecipient {
+    /// The index of the group, from zero.
+    pub group: usize,
+    /// The index of the share within the group, from zero, or `None` for
+    /// every share in the group.
+    pub member: Option<usize>,
+    /// Identifies the recipient in a `'holder'` assertion on each of their
+    /// shares.
+    pub holder: Envelope,
+    /// The keys the shares are sealed to.
+    pub public_keys: Vec<PublicKeys>,
+}
+
+impl ShareRecipient {
+    fn includes(&self, group: usize, member: usize) -> bool {
+        self.group == group && self.member.is_none_or(|m| m == member)
+    }
+}
+
+/// Split an envelope into SSKR shares, grouped as in `spec`.
+///
+/// The envelope is wrapped and encrypted with `content_key`, or with an
+/// ephemeral key if it is `None`. Each share can also be decrypted by each of
+/// the `recipients`.
+///
+/// Shares assigned to any of the `share_recipients` are wrapped and sealed to
+/// them with a key of their own, so that opening a share reveals only the
+/// share and not the content key.
+pub fn split(envelope: &Envelope, spec: &SSKRSpec, content_key: Option<&SymmetricKey>, recipients: &[PublicKeys], share_recipients: &[ShareRecipient]) -> Result<Vec<Vec<Envelope>>> {
+    let content_key = content_key.cloned().unwrap_or_default();
+    let encrypted = envelope.wrap_envelope().encrypt_subject(&content_key)?;
+    let groups = encrypted.sskr_split(spec, &content_key)?;
+    for share_recipient in share_recipients {
+        let Some(group) = groups.get(share_recipient.group) else {
+            bail!("share recipient group {} is not in the SSKR spec", share_recipient.group + 1);
+        };
+        if let Some(member) = share_recipient.member.filter(|member| *member >= group.len()) {
+            bail!("share recipient member {} is not in group {}", member + 1, share_recipient.group + 1);
+        }
+    }
+    groups.into_iter().enumerate()
+        .map(|(group, shares)| shares.into_iter().enumerate()
+            .map(|(member, share)| {
+                let share = recipients.iter().fold(share, |share, recipient| share.add_recipient(recipient, &content_key));
+                let holders = share_recipients.iter()
+                    .filter(|share_recipient| share_recipient.includes(group, member))
+                    .collect::<Vec<_>>();
+                if holders.is_empty() {
+                    return Ok(share);
+                }
+                let share_key = SymmetricKey::new();
+                let sealed = share.wrap_envelope().encrypt_subject(&share_key)?;
+                Ok(holders.iter().fold(sealed, |sealed, holder| holder.public_keys.iter().fold(
+                    sealed.add_assertion(known_values::HOLDER, holder.holder.clone()),
+                    |sealed, public_keys| sealed.add_recipient(public_keys, &share_key),
+                )))
+            })
+            .collect())
+        .collect()
+}
+
+/// Join a sufficient set of SSKR shares back into the original envelope.
+///
+/// Shares that were sealed to a share recipient may be given once decrypted,
+/// while still wrapped.
+pub fn join(shares: &[Envelope]) -> Result<Envelope> {
+    if shares.is_empty() {
+        bail!("No share envelopes provided");
+    }
+    let shares = shares.iter()
+        .map(|share| if share.subject().is_wrapped() { share.subject().unwrap_envelope() } else { Ok(share.clone()) })
+        .collect::<Result<Vec<_>>>()?;
+    Envelope::sskr_join(&shares.iter().collect::<Vec<_>>())?.unwrap_envelope()
+}
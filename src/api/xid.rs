@@ -0,0 +1,315 @@
+//! Creating and editing XID documents (`envelope xid`).
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Result};
+use bc_components::{PrivateKeyBase, PrivateKeys, PublicKeys, Signature, Verifier, XIDProvider, URI, XID};
+use bc_envelope::prelude::*;
+use bc_xid::{Delegate, HasName, HasPermissions, Key, Privilege, Service, XIDDocument};
+use known_values::{KEY, PRIVATE_KEY};
+
+/// A key given to a XID document, with or without its private key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputKey {
+    Public(PublicKeys),
+    Private(PrivateKeyBase),
+}
+
+impl InputKey {
+    fn to_key(&self) -> Key {
+        match self {
+            InputKey::Private(private_key_base) => Key::new_with_private_key_base(private_key_base.clone()),
+            InputKey::Public(public_keys) => Key::new(public_keys.clone()),
+        }
+    }
+}
+
+/// The name, endpoints, and permissions to give a key. Empty fields leave the
+/// key unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct KeyOptions {
+    pub name: String,
+    pub endpoints: Vec<URI>,
+    pub permissions: Vec<Privilege>,
+}
+
+impl KeyOptions {
+    fn apply(&self, key: &mut Key) {
+        if !self.name.is_empty() {
+            key.set_name(&self.name);
+        }
+        for uri in &self.endpoints {
+            key.add_endpoint(uri.clone());
+        }
+        if !self.permissions.is_empty() {
+            key.clear_all_permissions();
+            for privilege in &self.permissions {
+                key.add_permission(privilege.clone());
+            }
+        }
+    }
+}
+
+/// The name, capability, permissions, keys, and delegates to give a service.
+/// Empty fields leave the service unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct ServiceOptions {
+    pub name: Option<String>,
+    pub capability: Option<String>,
+    pub permissions: Vec<Privilege>,
+    pub keys: Vec<PublicKeys>,
+    pub delegates: Vec<XIDDocument>,
+}
+
+impl ServiceOptions {
+    fn apply(&self, xid_document: &XIDDocument, service: &mut Service) -> Result<()> {
+        if let Some(name) = self.name.as_deref().filter(|name| !name.is_empty()) {
+            service.set_name(name);
+        }
+        if let Some(capability) = self.capability.as_deref().filter(|capability| !capability.is_empty()) {
+            service.set_capability(capability);
+        }
+        if !self.permissions.is_empty() {
+            service.clear_all_permissions();
+            for privilege in &self.permissions {
+                service.add_allow(privilege.clone());
+            }
+        }
+        if !self.keys.is_empty() {
+            service.key_referenecs_mut().clear();
+            for key in &self.keys {
+                xid_document.check_contains_key(key)?;
+                service.add_key(key)?;
+            }
+        }
+        if !self.delegates.is_empty() {
+            service.delegate_references_mut().clear();
+            for delegate in &self.delegates {
+                xid_document.check_contains_delegate(delegate)?;
+                service.add_delegate(delegate)?;
+            }
+        }
+        xid_document.check_service_consistency(service)
+    }
+}
+
+/// Create a new XID document from an inception key.
+pub fn new_document(inception_key: &InputKey, options: &KeyOptions) -> Result<XIDDocument> {
+    let mut xid_document = match inception_key {
+        InputKey::Private(private_key_base) => XIDDocument::new_with_private_key_base(private_key_base.clone()),
+        InputKey::Public(public_keys) => XIDDocument::new(public_keys.clone()),
+    };
+    let mut key = xid_document.keys().iter().next().unwrap().clone();
+    xid_document.take_key(&key);
+    options.apply(&mut key);
+    xid_document.add_key(key)?;
+    Ok(xid_document)
+}
+
+/// Add a key to the XID document.
+pub fn add_key(xid_document: &mut XIDDocument, key: &InputKey, options: &KeyOptions) -> Result<()> {
+    let mut key = key.to_key();
+    options.apply(&mut key);
+    xid_document.add_key(key)
+}
+
+/// Update the name, endpoints, or permissions of the key in the XID document
+/// with the given public keys.
+pub fn update_key(xid_document: &mut XIDDocument, public_keys: &PublicKeys, options: &KeyOptions) -> Result<()> {
+    let mut key = xid_document
+        .find_key_by_public_keys(public_keys)
+        .cloned()
+        .ok_or_else(|| anyhow!("Key not found"))?;
+    xid_document.take_key(&key);
+    options.apply(&mut key);
+    xid_document.add_key(key)
+}
+
+/// Add a service to the XID document. Its keys and delegates must already be
+/// in the document.
+pub fn add_service(xid_document: &mut XIDDocument, uri: URI, options: &ServiceOptions) -> Result<()> {
+    let mut service = Service::new(uri);
+    options.apply(xid_document, &mut service)?;
+    xid_document.add_service(service)
+}
+
+/// Update the service in the XID document with the given URI.
+pub fn update_service(xid_document: &mut XIDDocument, uri: &URI, options: &ServiceOptions) -> Result<()> {
+    let mut service = xid_document
+        .find_service_by_uri(uri)
+        .cloned()
+        .ok_or_else(|| anyhow!("Service not found"))?;
+    xid_document.take_service(uri);
+    options.apply(xid_document, &mut service)?;
+    xid_document.add_service(service)
+}
+
+/// Add a delegate to the XID document with the given permissions.
+pub fn add_delegate(xid_document: &mut XIDDocument, delegate: XIDDocument, permissions: &[Privilege]) -> Result<()> {
+    let delegate = new_delegate(delegate, permissions)?;
+    xid_document.add_delegate(delegate)
+}
+
+/// Replace a delegate in the XID document, giving it the given permissions.
+pub fn update_delegate(xid_document: &mut XIDDocument, delegate: XIDDocument, permissions: &[Privilege]) -> Result<()> {
+    let delegate = new_delegate(delegate, permissions)?;
+    xid_document.take_delegate(&delegate);
+    xid_document.add_delegate(delegate)
+}
+
+fn new_delegate(xid_document: XIDDocument, permissions: &[Privilege]) -> Result<Delegate> {
+    if permissions.is_empty() {
+        bail!("At least one permission must be granted to the delegate.");
+    }
+    let mut delegate = Delegate::new(xid_document);
+    // If `All` is in the permissions, just add it.
+    if permissions.contains(&Privilege::All) {
+        delegate.add_allow(Privilege::All);
+    } else {
+        for permission in permissions {
+            delegate.add_allow(permission.clone());
+        }
+    }
+    Ok(delegate)
+}
+
+/// Identifies one of a XID document's keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySelector {
+    Name(String),
+    Public(PublicKeys),
+}
+
+/// Whether the key or delegate holds the privilege, directly or through
+/// `All`.
+fn holds_privilege(holder: &impl HasPermissions, privilege: Privilege) -> bool {
+    let holds = |privileges: &HashSet<Privilege>| privileges.contains(&privilege) || privileges.contains(&Privilege::All);
+    holds(holder.allow()) && !holds(holder.deny())
+}
+
+/// Whether the key or delegate holds the `Sign` privilege, directly or
+/// through `All`.
+pub fn can_sign(holder: &impl HasPermissions) -> bool {
+    holds_privilege(holder, Privilege::Sign)
+}
+
+/// Whether the key holds the `Encrypt` privilege, directly or through `All`.
+pub fn can_encrypt(key: &Key) -> bool {
+    holds_privilege(key, Privilege::Encrypt)
+}
+
+/// The keys of the XID document that include their private keys and hold the
+/// `Encrypt` privilege, and so may decrypt messages sent to the document.
+pub fn decryption_keys(xid_document: &XIDDocument) -> Vec<&Key> {
+    xid_document.keys().iter().filter(|key| key.private_keys().is_some() && can_encrypt(key)).collect()
+}
+
+/// The public keys of the XID document's keys that hold the `Sign` privilege.
+///
+/// If `follow_delegates` is set, the signing keys of each delegate that holds
+/// the `Sign` privilege are included as well, recursively.
+pub fn verifying_keys(xid_document: &XIDDocument, follow_delegates: bool) -> Vec<PublicKeys> {
+    let mut keys = Vec::new();
+    let mut visited = HashSet::new();
+    add_verifying_keys(xid_document, follow_delegates, &mut visited, &mut keys);
+    keys
+}
+
+fn add_verifying_keys(xid_document: &XIDDocument, follow_delegates: bool, visited: &mut HashSet<XID>, keys: &mut Vec<PublicKeys>) {
+    if !visited.insert(xid_document.xid()) {
+        return;
+    }
+    keys.extend(xid_document.keys().iter().filter(|key| can_sign(*key)).map(|key| key.public_keys().clone()));
+    if follow_delegates {
+        for delegate in xid_document.delegates().iter().filter(|delegate| can_sign(*delegate)) {
+            add_verifying_keys(&delegate.controller().read(), follow_delegates, visited, keys);
+        }
+    }
+}
+
+/// A verifier for the principal identified by a XID document: a signature is
+/// valid if any of the document's `verifying_keys` made it.
+///
+/// Used in place of the individual keys, a signature threshold counts each
+/// document at most once, however many of its keys signed.
+#[derive(Debug, Clone)]
+pub struct XIDVerifier(Vec<PublicKeys>);
+
+impl XIDVerifier {
+    pub fn new(xid_document: &XIDDocument, follow_delegates: bool) -> Self {
+        Self(verifying_keys(xid_document, follow_delegates))
+    }
+
+    pub fn keys(&self) -> &[PublicKeys] {
+        &self.0
+    }
+}
+
+impl Verifier for XIDVerifier {
+    fn verify(&self, signature: &Signature, message: &dyn AsRef<[u8]>) -> bool {
+        self.0.iter().any(|key| key.verify(signature, message))
+    }
+}
+
+/// The key of the XID document to sign with. It must include its private
+/// keys and hold the `Sign` privilege.
+///
+/// If no key is selected, the inception key is used if it can sign, and
+/// otherwise the only key that can.
+pub fn signing_key<'a>(xid_document: &'a XIDDocument, selector: Option<&KeySelector>) -> Result<&'a Key> {
+    let usable = |key: &Key| key.private_keys().is_some() && can_sign(key);
+    let key = match selector {
+        Some(KeySelector::Name(name)) => xid_document.keys().iter().find(|key| key.name() == name)
+            .ok_or_else(|| anyhow!("XID document has no key named {}", name))?,
+        Some(KeySelector::Public(public_keys)) => xid_document.find_key_by_public_keys(public_keys)
+            .ok_or_else(|| anyhow!("XID document has no such key"))?,
+        None => {
+            if let Some(key) = xid_document.inception_key().filter(|key| usable(key)) {
+                return Ok(key);
+            }
+            let keys = xid_document.keys().iter().filter(|key| usable(key)).collect::<Vec<_>>();
+            match keys.as_slice() {
+                [key] => return Ok(key),
+                [] => bail!("XID document has no private key with the Sign privilege"),
+                _ => bail!("XID document has several signing keys; select one by name or public keys"),
+            }
+        }
+    };
+    if key.private_keys().is_none() {
+        bail!("XID document does not include the private key of the selected key");
+    }
+    if !can_sign(key) {
+        bail!("The selected key does not hold the Sign privilege");
+    }
+    Ok(key)
+}
+
+/// Read a XID document from an unsigned envelope, including the private keys
+/// of its keys.
+///
+/// `XIDDocument::from_unsigned_envelope` prints each private key it reads to
+/// stdout, so the private keys are removed before parsing the document and
+/// restored afterwards.
+pub fn read_document(envelope: &Envelope) -> Result<XIDDocument> {
+    let mut stripped = envelope.clone();
+    let mut private_keys = Vec::new();
+    for assertion in envelope.assertions_with_predicate(KEY) {
+        let key = assertion.try_object()?;
+        if let Some(private_key_assertion) = key.optional_assertion_with_predicate(PRIVATE_KEY)? {
+            let private_key_cbor = private_key_assertion.subject().try_object()?.try_leaf()?;
+            private_keys.push((PublicKeys::try_from(key.subject().try_leaf()?)?, PrivateKeys::try_from(private_key_cbor)?));
+            stripped = stripped.remove_assertion(assertion)
+                .add_assertion(KEY, key.remove_assertion(private_key_assertion));
+        }
+    }
+    let mut xid_document = XIDDocument::from_unsigned_envelope(&stripped)?;
+    for (public_keys, private_keys) in private_keys {
+        let key = xid_document.take_key(&public_keys).ok_or_else(|| anyhow!("Key not found in XID document"))?;
+        let mut restored = Key::new_with_private_keys(private_keys, public_keys);
+        restored.set_name(key.name());
+        restored.endpoints_mut().extend(key.endpoints().iter().cloned());
+        *HasPermissions::permissions_mut(&mut restored) = key.permissions().clone();
+        xid_document.add_key(restored)?;
+    }
+    Ok(xid_document)
+}
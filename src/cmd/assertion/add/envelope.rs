@@ -1,6 +1,6 @@
 use clap::Args;
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, utils::read_envelope};
 use bc_envelope::prelude::*;
 use anyhow::Result;
 
@@ -26,10 +26,10 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
-        let assertion = Envelope::from_ur_string(&self.assertion)?;
-        Ok(envelope.add_assertion_envelope_salted(assertion, self.salted)?.ur_string())
+        let assertion = read_envelope(Some(&self.assertion))?;
+        crate::api::assertion::add(&envelope, assertion, self.salted)
     }
 }
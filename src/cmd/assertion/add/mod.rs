@@ -25,4 +25,11 @@ impl crate::exec::Exec for CommandArgs {
             AddCommands::PredObj(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            AddCommands::Envelope(args) => args.exec_output(),
+            AddCommands::PredObj(args) => args.exec_output(),
+        }
+    }
 }
@@ -45,10 +45,10 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         let assertion = self.assertion_envelope()?;
-        Ok(envelope.add_assertion_envelope_salted(assertion, self.salted)?.ur_string())
+        crate::api::assertion::add(&envelope, assertion, self.salted)
     }
 }
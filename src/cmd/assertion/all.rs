@@ -1,5 +1,4 @@
-use bc_envelope::prelude::*;
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, exec::Output};
 use clap::Args;
 use anyhow::Result;
 
@@ -19,9 +18,11 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = self.read_envelope()?;
-        let assertions = envelope.assertions();
-        let output = assertions.iter().map(|a| a.ur_string()).collect::<Vec<String>>().join("\n");
-        Ok(output)
+        Ok(Output::Envelopes(envelope.assertions(), "\n"))
     }
 }
@@ -1,6 +1,6 @@
 use bc_envelope::prelude::*;
 use clap::Args;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 
 use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
 
@@ -21,11 +21,9 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
-        let assertions = envelope.assertions();
-        let assertion = assertions.get(self.index).ok_or_else(|| anyhow!("Index out of bounds"))?;
-        Ok(assertion.ur_string())
+        crate::api::assertion::at(&envelope, self.index)
     }
 }
@@ -1,6 +1,6 @@
 use clap::Args;
 
-use crate::{pred_obj_args::{PredObjArgsLike, PredObjArgs}, data_types::DataType};
+use crate::{pred_obj_args::{PredObjArgsLike, PredObjArgs}, data_types::{DataType, parse_data_type_to_envelope}};
 use bc_envelope::prelude::*;
 use anyhow::Result;
 
@@ -36,12 +36,10 @@ impl PredObjArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
-        let mut result = self.pred_obj_args.assertion_envelope()?;
-        if self.salted {
-            result = result.add_salt();
-        }
-        Ok(result.ur_string())
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        let predicate = parse_data_type_to_envelope(self.pred_type(), Some(self.pred_value()), self.pred_tag())?;
+        let object = parse_data_type_to_envelope(self.obj_type(), Some(self.obj_value()), self.obj_tag())?;
+        Ok(crate::api::assertion::create(predicate, object, self.salted))
     }
 }
@@ -25,4 +25,11 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Predicate(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Object(args) => args.exec_output(),
+            SubCommands::Predicate(args) => args.exec_output(),
+        }
+    }
 }
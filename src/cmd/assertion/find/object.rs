@@ -1,8 +1,7 @@
-use bc_envelope::prelude::*;
 use clap::Args;
 use anyhow::Result;
 
-use crate::{subject_args::{SubjectArgs, SubjectArgsLike}, envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, data_types::{DataType, parse_data_type_to_envelope}};
+use crate::{subject_args::{SubjectArgs, SubjectArgsLike}, envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, data_types::{DataType, parse_data_type_to_envelope}, exec::Output};
 
 /// Find all assertions having the given object.
 #[derive(Debug, Args)]
@@ -37,12 +36,13 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = self.read_envelope()?;
         let object = parse_data_type_to_envelope(self.subject_type(), self.subject_value(), self.ur_tag())?;
-        let assertions = envelope.clone().assertions();
-        let result = assertions
-            .iter().filter(|&a| a.clone().as_object().unwrap().digest() == object.digest()).cloned().collect::<Vec<_>>()
-            .iter().map(|a| a.ur_string()).collect::<Vec<String>>().join("\n");
-        Ok(result)
+        let result = crate::api::assertion::find_by_object(&envelope, &object);
+        Ok(Output::Envelopes(result, "\n"))
     }
 }
@@ -25,4 +25,11 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::PredObj(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Envelope(args) => args.exec_output(),
+            SubCommands::PredObj(args) => args.exec_output(),
+        }
+    }
 }
@@ -42,10 +42,10 @@ impl PredObjArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         let assertion = self.assertion_envelope()?;
-        Ok(envelope.remove_assertion(assertion).ur_string())
+        Ok(crate::api::assertion::remove(&envelope, assertion))
     }
 }
@@ -30,11 +30,11 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         let payload = read_envelope(Some(&self.payload))?;
         let e = envelope.add_attachment(payload, self.vendor.as_str(), self.conforms_to.as_deref());
-        Ok(e.ur_string())
+        Ok(e)
     }
 }
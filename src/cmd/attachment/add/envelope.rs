@@ -23,12 +23,12 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         let attachment = read_envelope(Some(&self.attachment))?;
         attachment.clone().validate_attachment()?;
         let e = envelope.add_assertion_envelope(attachment.clone())?;
-        Ok(e.ur_string())
+        Ok(e)
     }
 }
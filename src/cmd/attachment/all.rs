@@ -1,8 +1,7 @@
 use clap::Args;
-use bc_envelope::prelude::*;
 use anyhow::Result;
 
-use crate::utils::read_envelope;
+use crate::{utils::read_envelope, exec::Output};
 
 /// Retrieve all the envelope's assertions.
 #[derive(Debug, Args)]
@@ -13,9 +12,12 @@ pub struct CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = read_envelope(self.envelope.as_deref())?;
         let attachments = envelope.attachments()?;
-        let result = attachments.into_iter().map(|a| a.ur_string()).collect::<Vec<_>>().join("\n");
-        Ok(result)
+        Ok(Output::Envelopes(attachments, "\n"))
     }
 }
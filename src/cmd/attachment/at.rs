@@ -12,11 +12,11 @@ pub struct CommandArgs {
     envelope: Option<String>,
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = read_envelope(self.envelope.as_deref())?;
         let attachments = &envelope.attachments()?;
         let attachment = attachments.get(self.index).ok_or_else(|| anyhow!("No attachment at index {}", self.index))?;
-        Ok(attachment.ur_string())
+        Ok(attachment.clone())
     }
 }
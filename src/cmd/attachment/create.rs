@@ -21,10 +21,10 @@ pub struct CommandArgs {
     payload: Option<String>,
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let payload = read_envelope(self.payload.as_deref())?;
         let assertion = Envelope::new_attachment(payload, self.vendor.as_str(), self.conforms_to.as_deref());
-        Ok(assertion.ur_string())
+        Ok(assertion)
     }
 }
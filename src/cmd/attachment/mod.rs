@@ -46,4 +46,18 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Find(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Add(args) => args.exec_output(),
+            SubCommands::All(args) => args.exec_output(),
+            SubCommands::At(args) => args.exec_output(),
+            SubCommands::ConformsTo(args) => args.exec_output(),
+            SubCommands::Count(args) => args.exec_output(),
+            SubCommands::Create(args) => args.exec_output(),
+            SubCommands::Payload(args) => args.exec_output(),
+            SubCommands::Vendor(args) => args.exec_output(),
+            SubCommands::Find(args) => args.exec_output(),
+        }
+    }
 }
@@ -12,9 +12,9 @@ pub struct CommandArgs {
     attachment: Option<String>,
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let attachment = read_envelope(self.attachment.as_deref())?;
-        Ok(attachment.attachment_payload()?.ur_string())
+        attachment.attachment_payload()
     }
 }
@@ -22,13 +22,9 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
-        Ok(if self.subject {
-            envelope.compress_subject()
-        } else {
-            envelope.compress()
-        }?.ur_string())
+        crate::api::compress::compress(&envelope, self.subject)
     }
 }
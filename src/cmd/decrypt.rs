@@ -1,9 +1,16 @@
 use anyhow::{bail, Result};
 use clap::Args;
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
-use bc_components::{SymmetricKey, PrivateKeyBase};
+use crate::{
+    api::{self, xid::decryption_keys},
+    cmd::xid::utils::read_xid_document_arg,
+    envelope_args::{EnvelopeArgs, EnvelopeArgsLike},
+    exec::Output,
+    utils::read_password,
+};
+use bc_components::{Decrypter, SymmetricKey, PrivateKeyBase, PublicKeysProvider, XIDProvider};
 use bc_envelope::prelude::*;
+use bc_xid::{HasName, XIDDocument};
 
 /// Decrypt the envelope's subject using the provided key.
 #[derive(Debug, Args)]
@@ -13,9 +20,26 @@ pub struct CommandArgs {
     #[arg(long, short, conflicts_with = "recipient")]
     key: Option<String>,
 
-    /// The recipient to whom the envelope's subject should be decrypted. (ur:crypto-prvkey-base)
+    /// Decrypt with the key derived from a password and the key derivation
+    /// parameters recorded on the envelope. Also decrypts elements encrypted
+    /// with `elide --action encrypt --password`. The password may be given as
+    /// `--password=PASSWORD`, and is otherwise read interactively from the
+    /// terminal.
+    #[arg(long, num_args = 0..=1, require_equals = true, conflicts_with_all = ["key", "recipient"])]
+    password: Option<Option<String>>,
+
+    /// The recipient to whom the envelope's subject should be decrypted. May be a
+    /// private key base (ur:crypto-prvkey-base), or a XID document (ur:xid) that includes
+    /// private keys, whose keys holding the `Encrypt` privilege are each tried.
+    ///
+    /// May be provided multiple times, in which case each key is tried in turn.
     #[arg(long, short)]
-    recipient: Option<String>,
+    recipient: Vec<String>,
+
+    /// Print the recipient key that decrypted the envelope instead of the
+    /// decrypted envelope.
+    #[arg(long, requires = "recipient")]
+    report_key: bool,
 
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
@@ -29,15 +53,67 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = self.read_envelope()?;
-        if let Some(key_ur) = &self.key {
+        let decrypted = if let Some(key_ur) = &self.key {
             let key = SymmetricKey::from_ur_string(key_ur)?;
-            Ok(envelope.decrypt_subject(&key)?.ur_string())
-        } else if let Some(recipient_ur) = &self.recipient {
-            let recipient = PrivateKeyBase::from_ur_string(recipient_ur)?;
-            Ok(envelope.decrypt_subject_to_recipient(&recipient)?.ur_string())
+            api::encrypt::decrypt(&envelope, &key)?
+        } else if !self.recipient.is_empty() {
+            let (decrypted, key) = self.decrypt_to_recipients(&envelope)?;
+            if self.report_key {
+                return Ok(Output::Text(key));
+            }
+            decrypted
+        } else if let Some(password) = &self.password {
+            let password = read_password("Decryption password: ", password.as_deref())?;
+            api::encrypt::decrypt_with_password(&envelope, &password)?
         } else {
-            bail!("missing key or recipient");
+            bail!("missing key, recipient, or password");
+        };
+        Ok(Output::Envelope(decrypted))
+    }
+}
+
+impl CommandArgs {
+    /// Decrypt to the first recipient key that can, returning the decrypted
+    /// envelope and a description of the key.
+    fn decrypt_to_recipients(&self, envelope: &Envelope) -> Result<(Envelope, String)> {
+        let mut private_key_bases: Vec<PrivateKeyBase> = Vec::new();
+        let mut xid_documents: Vec<XIDDocument> = Vec::new();
+        for r in &self.recipient {
+            if let Ok(key) = PrivateKeyBase::from_ur_string(r) {
+                private_key_bases.push(key);
+            } else if let Some(xid_document) = read_xid_document_arg(r)? {
+                xid_documents.push(xid_document);
+            } else {
+                bail!("invalid recipient: {}", r);
+            }
+        }
+        let mut recipients: Vec<(&dyn Decrypter, String)> = Vec::new();
+        for key in private_key_bases.iter() {
+            recipients.push((key as &dyn Decrypter, Envelope::new(key.public_keys()).format_flat()));
+        }
+        for xid_document in xid_documents.iter() {
+            let keys = decryption_keys(xid_document);
+            if keys.is_empty() {
+                bail!("XID document recipient has no private keys with the Encrypt privilege");
+            }
+            let xid = Envelope::new(xid_document.xid()).format_flat();
+            for key in keys {
+                let public_keys = Envelope::new(key.public_keys().clone()).format_flat();
+                let description = if key.name().is_empty() {
+                    format!("{} of {}", public_keys, xid)
+                } else {
+                    format!("{} \"{}\" of {}", public_keys, key.name(), xid)
+                };
+                recipients.push((key.private_keys().unwrap() as &dyn Decrypter, description));
+            }
         }
+        let decrypters = recipients.iter().map(|(decrypter, _)| *decrypter).collect::<Vec<_>>();
+        let (decrypted, index) = api::encrypt::decrypt_to_any_recipient(envelope, &decrypters)?;
+        Ok((decrypted, recipients.swap_remove(index).1))
     }
 }
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use clap::{Args, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    api::diff::{diff, Difference, DifferenceKind},
+    envelope_args::{EnvelopeArgs, EnvelopeArgsLike},
+    utils::read_envelope,
+};
+
+/// Compare two envelopes structurally.
+///
+/// Elements are matched by digest. Reports assertions that were added or
+/// removed, assertions whose objects changed, and elements that were elided,
+/// encrypted, or compressed in one envelope but not the other. Prints nothing
+/// if the envelopes are identical.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Output format.
+    #[arg(long = "type", id = "TYPE", default_value = "text")]
+    diff_type: DiffType,
+
+    /// The original envelope.
+    old: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum DiffType {
+    /// One line per difference: its kind, path, and the elements involved.
+    Text,
+    /// A JSON array with one object per difference.
+    Json,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl crate::exec::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let old = read_envelope(Some(&self.old))?;
+        let new = self.read_envelope()?;
+        let differences = diff(&old, &new);
+        Ok(match self.diff_type {
+            DiffType::Text => differences.iter().map(difference_text).collect::<Vec<_>>().join("\n"),
+            DiffType::Json => serde_json::to_string_pretty(&Value::Array(differences.iter().map(difference_json).collect()))?,
+        })
+    }
+}
+
+fn difference_text(difference: &Difference) -> String {
+    let old = difference.old.as_ref().map(|e| e.format_flat()).unwrap_or_default();
+    let new = difference.new.as_ref().map(|e| e.format_flat()).unwrap_or_default();
+    let detail = match difference.kind {
+        DifferenceKind::Added | DifferenceKind::Revealed => new,
+        DifferenceKind::Changed => format!("{} -> {}", old, new),
+        _ => old,
+    };
+    format!("{} {} {}", difference.kind.name(), difference.path_string(), detail)
+}
+
+fn difference_json(difference: &Difference) -> Value {
+    let element = |envelope: &Option<Envelope>| match envelope {
+        Some(envelope) => json!({
+            "digest": envelope.digest().ur_string(),
+            "summary": envelope.format_flat(),
+        }),
+        None => Value::Null,
+    };
+    json!({
+        "kind": difference.kind.name(),
+        "path": difference.path,
+        "old": element(&difference.old),
+        "new": element(&difference.new),
+    })
+}
@@ -2,8 +2,8 @@ use std::collections::HashSet;
 
 use clap::{Args, ValueEnum};
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
-use bc_envelope::prelude::*;
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, envelope_json::case_name};
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*};
 use anyhow::Result;
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -26,6 +26,11 @@ pub struct CommandArgs {
     #[arg(long = "hex", default_value = "false")]
     hex: bool,
 
+    /// Print one line per element with its digest, its path from the root,
+    /// its case, and a short summary, in tree order.
+    #[arg(long = "annotate", default_value = "false")]
+    annotate: bool,
+
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
 }
@@ -44,9 +49,60 @@ impl crate::exec::Exec for CommandArgs {
             Depth::Shallow => envelope.shallow_digests(),
             Depth::Deep => envelope.deep_digests(),
         };
+        if self.annotate {
+            let mut lines = Vec::new();
+            annotate(&envelope, &digests, "", self.hex, &mut lines);
+            return Ok(lines.join("\n"));
+        }
         let mut ordered_digests = digests.iter().cloned().collect::<Vec<_>>();
         ordered_digests.sort();
         let output = ordered_digests.iter().map(|d| if self.hex { d.hex() } else { d.ur_string() }).collect::<Vec<String>>().join(" ");
         Ok(output)
     }
 }
+
+/// The maximum length of an element's summary.
+const SUMMARY_LENGTH: usize = 40;
+
+/// Add a line for each element whose digest is in `digests`. Paths name the
+/// subject of a node `subj`, an assertion by its predicate, the predicate and
+/// object of an assertion `pred` and `obj`, and the content of a wrapped
+/// envelope `wrapped`.
+fn annotate(envelope: &Envelope, digests: &HashSet<Digest>, path: &str, hex: bool, lines: &mut Vec<String>) {
+    let digest = envelope.digest();
+    if digests.contains(&digest) {
+        let digest = if hex { digest.hex() } else { digest.ur_string() };
+        let summary = match envelope.case() {
+            EnvelopeCase::Leaf { .. } | EnvelopeCase::KnownValue { .. } => {
+                with_format_context!(|context: &FormatContext| envelope.summary(SUMMARY_LENGTH, context))
+            }
+            _ => {
+                let flat = envelope.format_flat();
+                if flat.chars().count() > SUMMARY_LENGTH {
+                    format!("{}…", flat.chars().take(SUMMARY_LENGTH - 1).collect::<String>())
+                } else {
+                    flat
+                }
+            }
+        };
+        lines.push(format!("{} {} {} {}", digest, if path.is_empty() { "/" } else { path }, case_name(envelope), summary));
+    }
+    match envelope.case() {
+        EnvelopeCase::Node { subject, assertions, .. } => {
+            annotate(subject, digests, &format!("{}/subj", path), hex, lines);
+            for assertion in assertions {
+                let label = match assertion.as_predicate() {
+                    Some(predicate) => predicate.format_flat(),
+                    None => assertion.short_id(),
+                };
+                annotate(assertion, digests, &format!("{}/{}", path, label), hex, lines);
+            }
+        }
+        EnvelopeCase::Wrapped { envelope, .. } => annotate(envelope, digests, &format!("{}/wrapped", path), hex, lines),
+        EnvelopeCase::Assertion(assertion) => {
+            annotate(&assertion.predicate(), digests, &format!("{}/pred", path), hex, lines);
+            annotate(&assertion.object(), digests, &format!("{}/obj", path), hex, lines);
+        }
+        _ => {}
+    }
+}
@@ -2,10 +2,10 @@ use std::collections::HashSet;
 
 use bc_components::SymmetricKey;
 use clap::{ValueEnum, Args};
-use anyhow::Result;
+use anyhow::{bail, Result};
 use bc_envelope::prelude::*;
 
-use crate::utils::parse_digests;
+use crate::{api, utils::{parse_digests, read_password}};
 
 /// The action to take on the elements.
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -21,30 +21,76 @@ pub enum Action {
 pub trait ElideArgsLike {
     fn action(&self) -> Action;
     fn key(&self) -> Option<&str>;
-    fn target(&self) -> &String;
+    fn password(&self) -> Option<Option<&str>>;
+    fn target(&self) -> Option<&str>;
+    fn predicates(&self) -> &[String];
+    fn objects(&self) -> &[String];
+    fn queries(&self) -> &[String];
 
-    fn get_target_set(&self) -> Result<HashSet<Digest>> {
-        parse_digests(self.target())
+    fn has_selectors(&self) -> bool {
+        !self.predicates().is_empty() || !self.objects().is_empty() || !self.queries().is_empty()
     }
 
-    fn get_action(&self) -> Result<ObscureAction> {
+    /// The digests in the target, plus those of the elements chosen by the
+    /// selectors. When revealing, the selected elements are revealed along
+    /// with the elements leading to them.
+    fn get_target_set(&self, envelope: &Envelope, revealing: bool) -> Result<HashSet<Digest>> {
+        let mut target = match self.target() {
+            Some(target) => parse_digests(target)?,
+            None if self.has_selectors() => HashSet::new(),
+            None => bail!("No target digests or selectors provided"),
+        };
+        let mut selected = Vec::new();
+        for predicate in self.predicates() {
+            selected.extend(api::query::find_assertions(envelope, predicate)?);
+        }
+        for object in self.objects() {
+            selected.extend(api::query::find_objects(envelope, object)?);
+        }
+        for query in self.queries() {
+            selected.extend(api::query::query(envelope, query)?);
+        }
+        let selected = selected.iter().map(|e| e.digest().into_owned()).collect::<HashSet<_>>();
+        if revealing {
+            target.extend(api::elide::revealing_set(envelope, &selected));
+        } else {
+            target.extend(selected);
+        }
+        Ok(target)
+    }
+
+    /// The action, and for password encryption the assertion recording the
+    /// key derivation, which is added to each encrypted element.
+    fn get_action(&self) -> Result<(ObscureAction, Option<Envelope>)> {
         let action = match self.action() {
             Action::Elide => ObscureAction::Elide,
             Action::Encrypt => {
+                if let Some(password) = self.password() {
+                    let password = read_password("Encryption password: ", password)?;
+                    let (key, kdf) = api::encrypt::new_password_key(&password)?;
+                    return Ok((ObscureAction::Encrypt(key), Some(kdf)));
+                }
                 let key = self.key().ok_or_else(|| anyhow::anyhow!("No key provided"))?;
                 let key = SymmetricKey::from_ur_string(key)?;
                 ObscureAction::Encrypt(key)
             },
             Action::Compress => ObscureAction::Compress,
         };
-        Ok(action)
+        Ok((action, None))
     }
 
     fn run(&self, envelope: Envelope, revealing: bool) -> Result<Envelope> {
-        let target = self.get_target_set()?;
-        let action = self.get_action()?;
-        let result = envelope.elide_set_with_action(&target, revealing, &action);
-        Ok(result)
+        let target = self.get_target_set(&envelope, revealing)?;
+        let (action, kdf) = self.get_action()?;
+        let result = if revealing {
+            api::elide::elide_revealing(&envelope, &target, &action)
+        } else {
+            api::elide::elide_removing(&envelope, &target, &action)
+        };
+        match (&action, kdf) {
+            (ObscureAction::Encrypt(key), Some(kdf)) => api::encrypt::add_kdf_assertions(&result, key, &kdf),
+            _ => Ok(result),
+        }
     }
 }
 
@@ -60,9 +106,49 @@ pub struct ElideArgs {
     #[arg(long)]
     key: Option<String>,
 
+    /// When action is `--encrypt`, encrypt with a key derived from a password
+    /// instead, recording the salt and key derivation parameters on each
+    /// encrypted element. The password may be given as `--password=PASSWORD`,
+    /// and is otherwise read interactively from the terminal. Ignored
+    /// otherwise.
+    #[arg(long, num_args = 0..=1, require_equals = true, conflicts_with = "key")]
+    password: Option<Option<String>>,
+
+    /// Select the assertions with this predicate anywhere in the envelope:
+    /// a bare word matching a string or known value, a "quoted" string, or a
+    /// 'quoted' known value. May be repeated.
+    #[arg(long = "predicate", id = "PREDICATE")]
+    predicates: Vec<String>,
+
+    /// Select the objects with this value anywhere in the envelope: a bare
+    /// word matching a string, known value, or number, a "quoted" string, or a
+    /// 'quoted' known value. May be repeated.
+    #[arg(long = "object", id = "OBJECT")]
+    objects: Vec<String>,
+
+    /// Select the nodes matching this path query, as used by the `query`
+    /// command. May be repeated.
+    #[arg(long = "query", id = "QUERY")]
+    queries: Vec<String>,
+
     /// The target set of digests: zero or more `ur:digest` or `ur:envelope`
-    /// separated by a single space.
-    target: String,
+    /// separated by a single space. May be omitted if selectors are given, in
+    /// which case a single positional argument is the target only if it
+    /// consists of `ur:digest`s.
+    target: Option<String>,
+}
+
+impl ElideArgs {
+    /// Split the positional arguments into the target and the envelope. When
+    /// selectors are given, the target may be omitted, so a single positional
+    /// argument is the target if it consists of `ur:digest`s, and otherwise
+    /// the envelope.
+    pub fn target_and_envelope<'a>(&'a self, envelope: Option<&'a str>) -> (Option<&'a str>, Option<&'a str>) {
+        match (self.target.as_deref(), envelope) {
+            (Some(target), None) if self.has_selectors() && !is_digest_list(target) => (None, Some(target)),
+            (target, envelope) => (target, envelope),
+        }
+    }
 }
 
 impl ElideArgsLike for ElideArgs {
@@ -74,7 +160,29 @@ impl ElideArgsLike for ElideArgs {
         self.key.as_deref()
     }
 
-    fn target(&self) -> &String {
-        &self.target
+    fn password(&self) -> Option<Option<&str>> {
+        self.password.as_ref().map(|password| password.as_deref())
     }
+
+    fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    fn predicates(&self) -> &[String] {
+        &self.predicates
+    }
+
+    fn objects(&self) -> &[String] {
+        &self.objects
+    }
+
+    fn queries(&self) -> &[String] {
+        &self.queries
+    }
+}
+
+/// Whether `s` is one or more `ur:digest`s separated by spaces.
+fn is_digest_list(s: &str) -> bool {
+    let mut urs = s.split_whitespace().peekable();
+    urs.peek().is_some() && urs.all(|ur| Digest::from_ur_string(ur).is_ok())
 }
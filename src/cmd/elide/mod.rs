@@ -26,4 +26,11 @@ impl crate::exec::Exec for CommandArgs {
             ElideCommands::Removing(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            ElideCommands::Revealing(args) => args.exec_output(),
+            ElideCommands::Removing(args) => args.exec_output(),
+        }
+    }
 }
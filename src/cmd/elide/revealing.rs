@@ -25,21 +25,37 @@ impl ElideArgsLike for CommandArgs {
         self.elide_args.key()
     }
 
-    fn target(&self) -> &String {
-        self.elide_args.target()
+    fn password(&self) -> Option<Option<&str>> {
+        self.elide_args.password()
+    }
+
+    fn target(&self) -> Option<&str> {
+        self.elide_args.target_and_envelope(self.envelope_args.envelope()).0
+    }
+
+    fn predicates(&self) -> &[String] {
+        self.elide_args.predicates()
+    }
+
+    fn objects(&self) -> &[String] {
+        self.elide_args.objects()
+    }
+
+    fn queries(&self) -> &[String] {
+        self.elide_args.queries()
     }
 }
 
 impl EnvelopeArgsLike for CommandArgs {
     fn envelope(&self) -> Option<&str> {
-        self.envelope_args.envelope()
+        self.elide_args.target_and_envelope(self.envelope_args.envelope()).1
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         let result_envelope = self.run(envelope, true)?;
-        Ok(result_envelope.ur_string())
+        Ok(result_envelope)
     }
 }
@@ -0,0 +1,51 @@
+use anyhow::{bail, Result};
+use clap::Args;
+
+use crate::{api, envelope_args::{EnvelopeArgs, EnvelopeArgsLike}};
+use bc_components::{PrivateKeyBase, PublicKeys, SymmetricKey};
+use bc_envelope::prelude::*;
+
+/// Add a recipient to an envelope whose subject is already encrypted.
+///
+/// The content key is sealed to the new recipient in a new `hasRecipient`
+/// assertion. The encrypted subject is not changed, so signatures on it
+/// remain valid.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The content key that encrypts the envelope's subject. (ur:crypto-key)
+    #[arg(long, short, conflicts_with = "existing")]
+    key: Option<String>,
+
+    /// The private key base of an existing recipient, used to recover the
+    /// content key. (ur:crypto-prvkey-base)
+    #[arg(long, short)]
+    existing: Option<String>,
+
+    /// The recipient to add. (ur:crypto-pubkeys)
+    recipient: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        let envelope = self.read_envelope()?;
+        let recipient = PublicKeys::from_ur_string(&self.recipient)?;
+        let content_key = if let Some(key) = &self.key {
+            SymmetricKey::from_ur_string(key)?
+        } else if let Some(existing) = &self.existing {
+            api::encrypt::content_key(&envelope, &PrivateKeyBase::from_ur_string(existing)?)?
+        } else {
+            bail!("missing key or existing recipient");
+        };
+        api::encrypt::add_recipient(&envelope, &content_key, &recipient)
+    }
+}
@@ -0,0 +1,80 @@
+pub mod add_recipient;
+pub mod remove_recipient;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, exec::ExecEnvelope, utils::read_password};
+use bc_components::{SymmetricKey, PublicKeys};
+use bc_envelope::prelude::*;
+
+/// Encrypt the envelope's subject using the provided key.
+///
+/// If the key is not provided and recipients are provided, an ephemerally-generated key is used.
+/// Alternatively, the key may be derived from a password.
+///
+/// The subcommands add or remove recipients of an envelope that is already encrypted.
+#[derive(Debug, Args)]
+#[group(skip)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: Option<SubCommands>,
+
+    /// The symmetric key to use to encrypt the envelope's subject. (ur:crypto-key)
+    ///
+    /// If not provided and recipients are provided, an ephemerally-generated key is used.
+    #[arg(long, short)]
+    key: Option<String>,
+
+    /// Encrypt with a key derived from a password, recording the salt and key
+    /// derivation parameters on the envelope. The password may be given as
+    /// `--password=PASSWORD`, and is otherwise read interactively from the
+    /// terminal.
+    #[arg(long, num_args = 0..=1, require_equals = true, conflicts_with_all = ["key", "recipient"])]
+    password: Option<Option<String>>,
+
+    /// The recipients to whom the envelope's subject should be encrypted. (ur:crypto-pubkeys)
+    ///
+    /// May be provided multiple times.
+    #[arg(long, short)]
+    recipient: Vec<String>,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommands {
+    AddRecipient(add_recipient::CommandArgs),
+    RemoveRecipient(remove_recipient::CommandArgs),
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        match &self.command {
+            Some(SubCommands::AddRecipient(args)) => return args.exec_envelope(),
+            Some(SubCommands::RemoveRecipient(args)) => return args.exec_envelope(),
+            None => {}
+        }
+        let envelope = self.read_envelope()?;
+
+        if let Some(password) = &self.password {
+            let password = read_password("Encryption password: ", password.as_deref())?;
+            return crate::api::encrypt::encrypt_with_password(&envelope, &password);
+        }
+
+        // Convert recipients to `PublicKeys`.
+        let recipients = self.recipient.iter().map(PublicKeys::from_ur_string).collect::<Result<Vec<PublicKeys>>>()?;
+
+        let key = self.key.as_deref().map(SymmetricKey::from_ur_string).transpose()?;
+
+        crate::api::encrypt::encrypt(&envelope, key.as_ref(), &recipients)
+    }
+}
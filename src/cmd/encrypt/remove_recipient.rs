@@ -0,0 +1,42 @@
+use anyhow::{bail, Result};
+use clap::Args;
+
+use crate::{api, envelope_args::{EnvelopeArgs, EnvelopeArgsLike}};
+use bc_components::PrivateKeyBase;
+use bc_envelope::prelude::*;
+
+/// Remove a recipient from an envelope whose subject is encrypted.
+///
+/// Removes the recipient's `hasRecipient` assertion. The encrypted subject is
+/// not changed, so signatures on it remain valid. Note that this does not
+/// revoke access for a recipient who already has the envelope.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The recipient to remove: either its private key base
+    /// (ur:crypto-prvkey-base), or the digest of its `hasRecipient` assertion
+    /// (ur:digest).
+    recipient: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        let envelope = self.read_envelope()?;
+        if let Ok(recipient) = PrivateKeyBase::from_ur_string(&self.recipient) {
+            api::encrypt::remove_recipient(&envelope, &recipient)
+        } else if let Ok(digest) = Digest::from_ur_string(&self.recipient) {
+            api::encrypt::remove_recipient_assertion(&envelope, &digest)
+        } else {
+            bail!("invalid recipient: {}", self.recipient);
+        }
+    }
+}
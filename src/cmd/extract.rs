@@ -3,7 +3,7 @@ use bc_components::{tags::TAG_ENVELOPE, Digest, ARID, URI, UUID, XID};
 use bc_envelope::prelude::*;
 use clap::{Args, ValueEnum};
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{document::envelope_to_document, envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, exec::Output};
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SubjectType {
@@ -37,6 +37,9 @@ pub enum SubjectType {
     /// Envelope (ur:envelope)
     Envelope,
 
+    /// JSON document (the inverse of `subject json`)
+    Json,
+
     /// Known Value (number or string)
     Known,
 
@@ -60,6 +63,9 @@ pub enum SubjectType {
 
     /// XID
     Xid,
+
+    /// YAML document (the inverse of `subject json`)
+    Yaml,
 }
 
 /// Extract the subject of the input envelope.
@@ -92,9 +98,9 @@ impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let string = match self.subject_type {
-            SubjectType::Assertion => extract_assertion(envelope)?,
-            SubjectType::Object => extract_object(envelope)?,
-            SubjectType::Predicate => extract_predicate(envelope)?,
+            SubjectType::Assertion => extract_assertion(envelope)?.map(|e| e.ur_string()).join("\n"),
+            SubjectType::Object => extract_object(envelope)?.ur_string(),
+            SubjectType::Predicate => extract_predicate(envelope)?.ur_string(),
 
             SubjectType::Arid => envelope.extract_subject::<ARID>()?.ur_string(),
             SubjectType::AridHex => envelope.extract_subject::<ARID>()?.hex(),
@@ -104,6 +110,7 @@ impl crate::exec::Exec for CommandArgs {
             SubjectType::Date => envelope.extract_subject::<dcbor::Date>()?.to_string(),
             SubjectType::Digest => envelope.extract_subject::<Digest>()?.ur_string(),
             SubjectType::Envelope => envelope.subject().ur_string(),
+            SubjectType::Json => serde_json::to_string_pretty(&envelope_to_document(&envelope)?)?,
             SubjectType::Known => extract_known_value_string(envelope)?,
             SubjectType::Number => envelope.extract_subject::<f64>()?.to_string(),
             SubjectType::String => envelope.extract_subject::<String>()?,
@@ -112,31 +119,43 @@ impl crate::exec::Exec for CommandArgs {
             SubjectType::Uuid => envelope.extract_subject::<UUID>()?.to_string(),
             SubjectType::Wrapped => envelope.unwrap_envelope()?.ur_string(),
             SubjectType::Xid => envelope.extract_subject::<XID>()?.ur_string(),
+            SubjectType::Yaml => serde_yaml::to_string(&envelope_to_document(&envelope)?)?.trim_end().to_string(),
         };
         Ok(string)
     }
+
+    fn exec_output(&self) -> Result<Output> {
+        let envelope = match self.subject_type {
+            SubjectType::Assertion => return Ok(Output::Envelopes(extract_assertion(self.read_envelope()?)?.to_vec(), "\n")),
+            SubjectType::Envelope => self.read_envelope()?.subject(),
+            SubjectType::Object => extract_object(self.read_envelope()?)?,
+            SubjectType::Predicate => extract_predicate(self.read_envelope()?)?,
+            SubjectType::Wrapped => self.read_envelope()?.unwrap_envelope()?,
+            _ => return self.exec().map(Output::Text),
+        };
+        Ok(Output::Envelope(envelope))
+    }
 }
 
-fn extract_assertion(envelope: Envelope) -> Result<String> {
+fn extract_assertion(envelope: Envelope) -> Result<[Envelope; 2]> {
     if let Some(assertion) = envelope.as_assertion() {
-        let pred_obj = [assertion.clone().as_predicate().unwrap(), assertion.as_object().unwrap()];
-        Ok(pred_obj.into_iter().map(|e| e.ur_string()).collect::<Vec<String>>().join("\n"))
+        Ok([assertion.clone().as_predicate().unwrap(), assertion.as_object().unwrap()])
     } else {
         bail!("Envelope is not an assertion.");
     }
 }
 
-fn extract_object(envelope: Envelope) -> Result<String> {
+fn extract_object(envelope: Envelope) -> Result<Envelope> {
     if let Some(assertion) = envelope.as_assertion() {
-        Ok(assertion.as_object().unwrap().ur_string())
+        Ok(assertion.as_object().unwrap())
     } else {
         bail!("Envelope is not an assertion.");
     }
 }
 
-fn extract_predicate(envelope: Envelope) -> Result<String> {
+fn extract_predicate(envelope: Envelope) -> Result<Envelope> {
     if let Some(assertion) = envelope.as_assertion() {
-        Ok(assertion.as_predicate().unwrap().ur_string())
+        Ok(assertion.as_predicate().unwrap())
     } else {
         bail!("Envelope is not an assertion.");
     }
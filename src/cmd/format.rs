@@ -2,7 +2,7 @@ use clap::{Args, ValueEnum};
 use dcbor::CBORTaggedEncodable;
 use anyhow::Result;
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{encoding::encode_multipart_ur, envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, envelope_graph::{envelope_to_dot, envelope_to_mermaid}, envelope_json::envelope_to_json};
 use bc_envelope::prelude::*;
 
 /// Print the envelope in textual format.
@@ -17,6 +17,10 @@ pub struct CommandArgs {
     #[arg(long)]
     hide_nodes: bool,
 
+    /// For `--type multipart` the maximum length of each fragment.
+    #[arg(long, default_value = "100")]
+    max_fragment_len: usize,
+
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
 }
@@ -39,6 +43,14 @@ enum FormatType {
     Cbor,
     /// UR format.
     UR,
+    /// Multipart UR format (fountain-coded), one part per line.
+    Multipart,
+    /// Structured JSON tree with the digest, case, and decoded value of every node.
+    Json,
+    /// Graphviz DOT graph of the envelope's structure.
+    Dot,
+    /// Mermaid flowchart of the envelope's structure.
+    Mermaid,
 }
 
 impl crate::exec::Exec for CommandArgs {
@@ -50,6 +62,10 @@ impl crate::exec::Exec for CommandArgs {
             FormatType::Diag => e.diagnostic(),
             FormatType::Cbor => hex::encode(e.tagged_cbor_data()),
             FormatType::UR => e.ur_string(),
+            FormatType::Multipart => encode_multipart_ur(&e.ur(), self.max_fragment_len)?.join("\n"),
+            FormatType::Json => serde_json::to_string_pretty(&envelope_to_json(&e))?,
+            FormatType::Dot => envelope_to_dot(&e),
+            FormatType::Mermaid => envelope_to_mermaid(&e),
         };
         Ok(output)
     }
@@ -7,8 +7,7 @@ pub mod pub_keys;
 pub mod seed;
 pub mod signer;
 pub mod verifier;
-pub mod signer_type;
-pub use signer_type::SignerType;
+pub use crate::api::generate::SignerType;
 pub mod hash_type;
 pub use hash_type::HashType;
 
@@ -22,6 +22,7 @@ pub struct CommandArgs {
 }
 
 impl CommandArgs {
+    #[allow(clippy::unnecessary_unwrap)]
     fn read_prv_keys(&self) -> Result<String> {
         let mut ur_string = String::new();
         if self.prv_keys.is_none() {
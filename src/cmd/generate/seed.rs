@@ -1,4 +1,4 @@
-use anyhow::{ bail, Result };
+use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
@@ -22,14 +22,7 @@ impl crate::exec::Exec for CommandArgs {
             let bytes = hex::decode(hex)?;
             seed = bc_components::Seed::new_opt(bytes, None, None, None)?;
         } else {
-            let count = self.count.unwrap();
-            if count < bc_components::Seed::MIN_SEED_LENGTH {
-                bail!("Seed length must be at least 16 bytes");
-            }
-            if count > 256 {
-                bail!("Seed length must be at most 256 bytes");
-            }
-            seed = bc_components::Seed::new_with_len(count)?;
+            seed = crate::api::generate::seed(self.count.unwrap())?;
         }
         Ok(seed.ur_string())
     }
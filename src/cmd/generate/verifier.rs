@@ -17,6 +17,7 @@ pub struct CommandArgs {
 }
 
 impl CommandArgs {
+    #[allow(clippy::unnecessary_unwrap)]
     fn read_prv_key(&self) -> Result<String> {
         let mut ur_string = String::new();
         if self.prv_key.is_none() {
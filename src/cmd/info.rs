@@ -1,16 +1,18 @@
+use std::path::Path;
+
 use anyhow::{bail, Result};
 use clap::Args;
 use ssh_key::{public::KeyData, HashAlg};
 
-use crate::utils::read_argument;
-use bc_components::{PrivateKeyBase, PublicKeys, Seed, Signature, SigningPrivateKey, SigningPublicKey};
+use crate::{api::encrypt::encryption_info, qr_image::{is_qr_image_path, read_ur_from_qr_image}, utils::read_argument};
+use bc_components::{EncapsulationScheme, PrivateKeyBase, PublicKeys, Seed, Signature, SigningPrivateKey, SigningPublicKey};
 use bc_envelope::prelude::*;
 
 /// Provide type and other information about the object.
 #[derive(Debug, Args)]
 #[group(skip)]
 pub struct CommandArgs {
-    /// The object to provide information for. If not provided, the object will be read from stdin.
+    /// The object to provide information for, or an image file or directory containing its QR code(s). If not provided, the object will be read from stdin.
     object: Option<String>,
 }
 
@@ -31,15 +33,37 @@ impl crate::exec::Exec for CommandArgs {
             result.push(fingerprint.to_randomart(&format!("[{}]", algorithm_formatted).to_uppercase()));
         }
 
-        let object = read_argument(self.object.as_deref())?;
+        let mut object = read_argument(self.object.as_deref())?;
+        if is_qr_image_path(object.trim()) {
+            object = read_ur_from_qr_image(Path::new(object.trim()))?;
+        }
         if object.trim().strip_prefix("ur:").is_some() {
-            let ur = UR::from_ur_string(&object)?;
+            let ur = UR::from_ur_string(object.trim())?;
             let ur_type = ur.ur_type_str();
             add(&mut result, "Format", format!("ur:{}", ur_type));
             match ur_type {
                 "envelope" => {
-                    let _envelope = Envelope::from_ur(&ur)?;
+                    let envelope = Envelope::from_ur(&ur)?;
                     add(&mut result, "Description", "Gordian Envelope");
+                    if let Some(info) = encryption_info(&envelope)? {
+                        add(&mut result, "Encryption", "ChaCha20-Poly1305");
+                        add(&mut result, "Nonce Size", format!("{} bytes", info.nonce_size));
+                        add(&mut result, "Subject Digest", if info.has_digest { "present" } else { "absent" });
+                        if let Some(kdf) = info.kdf {
+                            add(&mut result, "Key Derivation", kdf);
+                        }
+                        add(&mut result, "Recipients", info.recipients.len().to_string());
+                        for (index, scheme) in info.recipients.iter().enumerate() {
+                            let scheme = match scheme {
+                                Some(EncapsulationScheme::X25519) => "X25519",
+                                Some(EncapsulationScheme::Kyber512) => "ML-KEM-512",
+                                Some(EncapsulationScheme::Kyber768) => "ML-KEM-768",
+                                Some(EncapsulationScheme::Kyber1024) => "ML-KEM-1024",
+                                None => "obscured",
+                            };
+                            add(&mut result, format!("Recipient {}", index + 1), scheme);
+                        }
+                    }
                 }
                 "seed" => {
                     let _seed = Seed::from_ur(&ur)?;
@@ -2,6 +2,7 @@ pub mod assertion;
 pub mod attachment;
 pub mod compress;
 pub mod decrypt;
+pub mod diff;
 pub mod digest;
 pub mod elide;
 pub mod encrypt;
@@ -11,8 +12,13 @@ pub mod format;
 pub mod generate;
 pub mod import;
 pub mod info;
+pub mod patch;
 pub mod proof;
+pub mod qr;
+pub mod query;
+pub mod run;
 pub mod salt;
+pub mod shell;
 pub mod sign;
 pub mod sskr;
 pub mod subject;
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, utils::read_envelope};
+
+/// Apply a patch to the envelope it was created from.
+///
+/// Fails if the envelope's digest does not match the patch's base digest. A
+/// signed patch is accepted; use `verify` to check its signature first.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The patch to apply.
+    patch: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        let patch = read_envelope(Some(&self.patch))?;
+        let envelope = self.read_envelope()?;
+        crate::api::patch::apply(&envelope, &patch)
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, utils::read_envelope};
+
+/// Create a patch that transforms one envelope into another.
+///
+/// The patch lists the assertions to remove and add, keyed by the digests of
+/// the elements of the original envelope that lead to them. It records the
+/// digests of both envelopes so it can only be applied to the original.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The original envelope.
+    old: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        let old = read_envelope(Some(&self.old))?;
+        let new = self.read_envelope()?;
+        crate::api::patch::create(&old, &new)
+    }
+}
@@ -0,0 +1,35 @@
+pub mod create;
+pub mod apply;
+pub use anyhow::Result;
+
+use clap::{Subcommand, Args};
+
+/// Create and apply patches between envelopes.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: SubCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommands {
+    Create(create::CommandArgs),
+    Apply(apply::CommandArgs),
+}
+
+impl crate::exec::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        match &self.command {
+            SubCommands::Create(args) => args.exec(),
+            SubCommands::Apply(args) => args.exec(),
+        }
+    }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Create(args) => args.exec_output(),
+            SubCommands::Apply(args) => args.exec_output(),
+        }
+    }
+}
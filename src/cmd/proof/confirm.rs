@@ -1,13 +1,16 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, utils::parse_digests};
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, exec::Output, utils::parse_digests};
 
 /// Confirm that an elided envelope contains a target digest using a proof.
 ///
-/// On success, print the original envelope so it can be piped to the next
-/// operation. On failure, exit with an error condition.
+/// In place of the envelope, a trusted `ur:digest` of it may be given on the
+/// command line, so the proof can be confirmed without holding the envelope.
+///
+/// On success, print the original envelope (or digest) so it can be piped to
+/// the next operation. On failure, exit with an error condition.
 #[derive(Debug, Args)]
 #[group(skip)]
 pub struct CommandArgs {
@@ -34,12 +37,24 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let envelope = self.read_envelope()?;
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let proof = Envelope::from_ur_string(&self.proof)?;
         let digests = parse_digests(&self.target)?;
-        if !envelope.clone().confirm_contains_set(&digests, &proof) {
-            bail!("Proof does not confirm target");
-        }
-        Ok(if self.silent { "".to_string() } else { envelope.ur_string() })
+        let output = match self.envelope().map(str::trim) {
+            Some(root) if root.starts_with("ur:digest/") => {
+                let root = Digest::from_ur_string(root)?;
+                crate::api::proof::confirm_digest(&root, &proof, &digests)?;
+                Output::Text(root.ur_string())
+            }
+            _ => {
+                let envelope = self.read_envelope()?;
+                crate::api::proof::confirm(&envelope, &proof, &digests)?;
+                Output::Envelope(envelope)
+            }
+        };
+        Ok(if self.silent { Output::Text("".to_string()) } else { output })
     }
 }
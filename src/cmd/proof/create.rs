@@ -1,4 +1,4 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
@@ -23,15 +23,10 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         let digests = parse_digests(&self.target)?;
-        let proof: Option<Envelope> = envelope.proof_contains_set(&digests);
-        if let Some(proof) = proof {
-            Ok(proof.ur_string())
-        } else {
-            bail!("No proof found for target set");
-        }
+        crate::api::proof::create(&envelope, &digests)
     }
 }
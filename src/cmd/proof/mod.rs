@@ -25,4 +25,11 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Confirm(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Create(args) => args.exec_output(),
+            SubCommands::Confirm(args) => args.exec_output(),
+        }
+    }
 }
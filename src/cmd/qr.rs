@@ -0,0 +1,202 @@
+use std::{io::Write, path::{Path, PathBuf}, thread, time::Duration};
+
+use anyhow::{bail, Result};
+use bc_ur::{MultipartEncoder, UR};
+use clap::{Args, ValueEnum};
+use image::{codecs::gif::{GifEncoder, Repeat}, Delay, Frame, Luma};
+use qrcode::{render::{svg, unicode::Dense1x2}, EcLevel, QrCode};
+
+use crate::{encoding::{decode_multipart_ur, encode_multipart_ur, is_multipart_ur}, utils::read_argument};
+
+/// Render a UR as a QR code.
+///
+/// URs are encoded in uppercase so the QR code can use the more compact
+/// alphanumeric mode. Multipart URs are rendered as a sequence of frames, one
+/// per part.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Output format.
+    #[arg(long = "type", id = "TYPE", default_value = "text")]
+    qr_type: QrType,
+
+    /// The file to write the QR code to. Required for `png` and `gif`. When
+    /// there are several frames, `png` and `svg` frames are written to
+    /// numbered files (e.g. `qr-1.png`, `qr-2.png`).
+    #[arg(long = "output", short)]
+    output_file: Option<PathBuf>,
+
+    /// Split the UR into a multipart UR with fragments of at most this length,
+    /// rendering one frame per part.
+    #[arg(long)]
+    max_fragment_len: Option<usize>,
+
+    /// For `--type text` display the frames in a loop until interrupted. For
+    /// multipart URs the fountain encoder keeps generating new parts.
+    #[arg(long)]
+    animate: bool,
+
+    /// For `--animate` and `--type gif` the number of frames per second.
+    #[arg(long, default_value = "4")]
+    fps: u32,
+
+    /// The size in pixels of each module for `png`, `svg`, and `gif`.
+    #[arg(long, default_value = "8")]
+    module_size: u32,
+
+    /// The UR to render, or the whitespace-separated parts of a multipart UR.
+    /// If not provided, the UR will be read from stdin.
+    ur: Option<String>,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum QrType {
+    /// UTF-8 block art for the terminal.
+    Text,
+    /// SVG image.
+    Svg,
+    /// PNG image.
+    Png,
+    /// Animated GIF with one frame per part.
+    Gif,
+}
+
+impl crate::exec::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        if self.fps == 0 {
+            bail!("--fps must be greater than zero");
+        }
+        let argument = read_argument(self.ur.as_deref())?;
+        let ur = if is_multipart_ur(&argument) {
+            decode_multipart_ur(&argument, false)?
+        } else {
+            UR::from_ur_string(argument.trim())?
+        };
+
+        if self.animate {
+            if self.qr_type != QrType::Text {
+                bail!("--animate is only supported for --type text");
+            }
+            return self.animate(&ur);
+        }
+
+        let frames = match self.max_fragment_len {
+            Some(max_fragment_len) => encode_multipart_ur(&ur, max_fragment_len)?,
+            None if is_multipart_ur(&argument) => argument.split_whitespace().map(|s| s.to_string()).collect(),
+            None => vec![ur.string()],
+        };
+        let codes = frames.iter().map(|frame| qr_code(frame)).collect::<Result<Vec<_>>>()?;
+
+        match self.qr_type {
+            QrType::Text => Ok(codes.iter().map(render_text).collect::<Vec<_>>().join("\n")),
+            QrType::Svg => {
+                let images = codes.iter().map(|code| self.render_svg(code)).collect::<Vec<_>>();
+                match &self.output_file {
+                    Some(path) => self.write_frames(path, &images, |path, image| Ok(std::fs::write(path, image)?)),
+                    None => Ok(images.join("\n")),
+                }
+            }
+            QrType::Png => {
+                let Some(path) = &self.output_file else {
+                    bail!("--output is required for --type png");
+                };
+                let images = codes.iter().map(|code| self.render_image(code)).collect::<Vec<_>>();
+                self.write_frames(path, &images, |path, image| Ok(image.save(path)?))
+            }
+            QrType::Gif => {
+                let Some(path) = &self.output_file else {
+                    bail!("--output is required for --type gif");
+                };
+                self.write_gif(path, &codes)?;
+                Ok(path.display().to_string())
+            }
+        }
+    }
+}
+
+impl CommandArgs {
+    fn animate(&self, ur: &UR) -> Result<String> {
+        let delay = Duration::from_millis(1000 / self.fps as u64);
+        let mut stdout = std::io::stdout();
+        let mut encoder = self.max_fragment_len
+            .map(|max_fragment_len| MultipartEncoder::new(ur, max_fragment_len))
+            .transpose()?;
+        loop {
+            let frame = match &mut encoder {
+                Some(encoder) => encoder.next_part()?,
+                None => ur.string(),
+            };
+            // Clear the screen and move the cursor home before each frame.
+            write!(stdout, "\x1b[2J\x1b[H{}", render_text(&qr_code(&frame)?))?;
+            stdout.flush()?;
+            thread::sleep(delay);
+        }
+    }
+
+    fn render_svg(&self, code: &QrCode) -> String {
+        code.render()
+            .module_dimensions(self.module_size, self.module_size)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build()
+    }
+
+    fn render_image(&self, code: &QrCode) -> image::GrayImage {
+        code.render::<Luma<u8>>()
+            .module_dimensions(self.module_size, self.module_size)
+            .build()
+    }
+
+    /// Write each frame to `path`, or to numbered files derived from `path`
+    /// if there is more than one frame. Returns the written paths, one per
+    /// line.
+    fn write_frames<T>(&self, path: &Path, frames: &[T], write: impl Fn(&Path, &T) -> Result<()>) -> Result<String> {
+        let paths = if frames.len() == 1 {
+            vec![path.to_path_buf()]
+        } else {
+            (1..=frames.len()).map(|index| numbered_path(path, index)).collect()
+        };
+        for (path, frame) in paths.iter().zip(frames) {
+            write(path, frame)?;
+        }
+        Ok(paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join("\n"))
+    }
+
+    fn write_gif(&self, path: &Path, codes: &[QrCode]) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut encoder = GifEncoder::new(file);
+        encoder.set_repeat(Repeat::Infinite)?;
+        let delay = Delay::from_numer_denom_ms(1000, self.fps);
+        // All frames of a multipart UR have the same length, so the QR codes
+        // have the same dimensions.
+        let frames = codes.iter().map(|code| {
+            let image = image::DynamicImage::ImageLuma8(self.render_image(code)).into_rgba8();
+            Frame::from_parts(image, 0, 0, delay)
+        });
+        encoder.encode_frames(frames)?;
+        Ok(())
+    }
+}
+
+/// Encode a UR string as a QR code, using uppercase for alphanumeric mode.
+fn qr_code(ur_string: &str) -> Result<QrCode> {
+    Ok(QrCode::with_error_correction_level(ur_string.to_uppercase(), EcLevel::L)?)
+}
+
+/// Render a QR code as UTF-8 block art, light on dark so it scans from a
+/// terminal with a dark background.
+fn render_text(code: &QrCode) -> String {
+    code.render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build()
+}
+
+fn numbered_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, index, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, index),
+    };
+    path.with_file_name(file_name)
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{envelope_args::{EnvelopeArgs, EnvelopeArgsLike}, exec::Output};
+
+/// Select nodes of the envelope using a path query.
+///
+/// Steps are applied in order, starting with the envelope:
+///
+/// - `.pred` selects the objects of assertions with predicate `pred`.
+///
+/// - `..pred` does the same at any depth, including within wrapped envelopes.
+///
+/// - `{}` selects the envelope wrapped in the subject.
+///
+/// - `[*]`, `[pred]`, `[pred=value]`, and `[=value]` keep the nodes that have
+///   any assertion with predicate `pred`, such an assertion with object
+///   `value`, or subject `value`.
+///
+/// Predicates and values may be `*`, a bare word matching a string, known
+/// value, or number, a "quoted" string, or a 'quoted' known value. For example
+/// `.knows[*].name` or `[isA=Person].dateOfBirth`.
+///
+/// Prints each selected node on its own line, or with `--digest`, the digests
+/// of the selected nodes separated by spaces, suitable as the target of
+/// `elide` or `proof create`.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Print the digests of the selected nodes instead of the nodes.
+    #[arg(long, short)]
+    digest: bool,
+
+    /// The query.
+    query: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args.envelope()
+    }
+}
+
+impl crate::exec::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
+        let envelope = self.read_envelope()?;
+        let nodes = crate::api::query::query(&envelope, &self.query)?;
+        Ok(if self.digest {
+            Output::Text(nodes.iter().map(|node| node.digest().ur_string()).collect::<Vec<_>>().join(" "))
+        } else {
+            Output::Envelopes(nodes, "\n")
+        })
+    }
+}
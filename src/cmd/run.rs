@@ -0,0 +1,269 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use bc_envelope::prelude::*;
+use clap::{error::ErrorKind, Args, Parser};
+
+use crate::{exec::{Exec, Output}, pipeline, utils::read_envelope, MainCommands};
+
+/// Run a script of subcommands in a single process.
+///
+/// Each line of the script is a subcommand, written as it would be after
+/// `envelope` on the command line. The commands operate on a current envelope
+/// kept in memory: a command that is not given an envelope uses the current
+/// envelope, and a command that produces an envelope replaces it. Any other
+/// output is printed. If the last command produces an envelope, it is printed.
+///
+/// A line of the form `NAME = <subcommand>` stores the output of the
+/// subcommand in the variable `NAME` instead. Variables are referenced as
+/// `$NAME` or `${NAME}` anywhere in a later line, and a line consisting only
+/// of `$NAME` makes the envelope in that variable the current envelope. Use
+/// `$$` for a literal `$`.
+///
+/// Lines starting with `#` are comments, and a line ending in `\` continues on
+/// the next line.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The script to run.
+    script: PathBuf,
+
+    /// Set a variable before the script runs, as `NAME=VALUE`. May be repeated.
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    vars: Vec<String>,
+}
+
+impl crate::exec::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        self.exec_output().map(|output| output.to_string())
+    }
+
+    /// The printed output of the script, or just the final envelope if
+    /// nothing else was printed.
+    fn exec_output(&self) -> Result<Output> {
+        let script = std::fs::read_to_string(&self.script)
+            .with_context(|| format!("Could not read script {}", self.script.display()))?;
+        let mut session = Session::new();
+        for var in &self.vars {
+            let Some((name, value)) = var.split_once('=') else {
+                bail!("Variables must be given as NAME=VALUE: {}", var);
+            };
+            session.set_variable(name, Output::Text(value.to_string()))?;
+        }
+
+        let mut printed = Vec::new();
+        let mut last = None;
+        for (line_number, line) in script_lines(&script) {
+            let result = session.run_line(&line)
+                .with_context(|| format!("Line {}: {}", line_number, line.trim()))?;
+            last = None;
+            match result {
+                Some(output) if output_envelope(&output).is_some() => last = Some(output),
+                Some(output) => {
+                    let text = output.to_string();
+                    if !text.is_empty() {
+                        printed.push(text);
+                    }
+                }
+                None => {}
+            }
+        }
+        match last {
+            Some(output) if printed.is_empty() => Ok(output),
+            Some(output) => {
+                printed.push(output.to_string());
+                Ok(Output::Text(printed.join("\n")))
+            }
+            None => Ok(Output::Text(printed.join("\n"))),
+        }
+    }
+}
+
+/// Join continued lines, returning each logical line with the number of the
+/// line it starts on.
+fn script_lines(script: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+    for (index, line) in script.lines().enumerate() {
+        let (line_number, mut text) = pending.take().unwrap_or((index + 1, String::new()));
+        match line.strip_suffix('\\') {
+            Some(line) => {
+                text.push_str(line);
+                pending = Some((line_number, text));
+            }
+            None => {
+                text.push_str(line);
+                lines.push((line_number, text));
+            }
+        }
+    }
+    lines.extend(pending);
+    lines
+}
+
+/// A line of a script, parsed as a subcommand.
+#[derive(Debug, Parser)]
+#[command(name = "envelope", no_binary_name = true)]
+pub struct ScriptCommand {
+    #[command(subcommand)]
+    command: MainCommands,
+}
+
+/// The state of a sequence of subcommands run in a single process: the
+/// current envelope and the named variables.
+pub struct Session {
+    variables: HashMap<String, Output>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        pipeline::begin();
+        Self { variables: HashMap::new() }
+    }
+
+    pub fn current_envelope(&self) -> Option<Envelope> {
+        pipeline::current_envelope()
+    }
+
+    pub fn set_current_envelope(&mut self, envelope: Option<Envelope>) {
+        pipeline::set_current_envelope(envelope);
+    }
+
+    pub fn variable_names(&self) -> Vec<String> {
+        let mut names = self.variables.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn set_variable(&mut self, name: &str, value: Output) -> Result<()> {
+        if !is_variable_name(name) {
+            bail!("Invalid variable name: {}", name);
+        }
+        self.variables.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Run one line of a script.
+    ///
+    /// Returns the output of the subcommand, or `None` if the line is blank, a
+    /// comment, or an assignment. If the output is an envelope, it also
+    /// becomes the current envelope.
+    pub fn run_line(&mut self, line: &str) -> Result<Option<Output>> {
+        let Some(words) = shlex::split(line) else {
+            bail!("Unbalanced quotes");
+        };
+        if words.is_empty() {
+            return Ok(None);
+        }
+        if words.len() >= 3 && words[1] == "=" && is_variable_name(&words[0]) {
+            let output = self.evaluate(&words[2..])?;
+            self.set_variable(&words[0], output)?;
+            return Ok(None);
+        }
+        let output = self.evaluate(&words)?;
+        if let Some(envelope) = output_envelope(&output) {
+            pipeline::set_current_envelope(Some(envelope));
+        }
+        Ok(Some(output))
+    }
+
+    /// Evaluate a variable reference or a subcommand.
+    fn evaluate(&self, words: &[String]) -> Result<Output> {
+        if let [word] = words {
+            if let Some(name) = variable_reference(word) {
+                return Ok(self.variable(name)?.clone());
+            }
+        }
+        let words = words.iter().map(|word| self.substitute(word)).collect::<Result<Vec<_>>>()?;
+        let command = match ScriptCommand::try_parse_from(&words) {
+            Ok(command) => command,
+            Err(e) if matches!(e.kind(), ErrorKind::DisplayHelp | ErrorKind::DisplayVersion) => {
+                return Ok(Output::Text(e.render().to_string().trim_end().to_string()));
+            }
+            Err(e) => bail!(e.render().to_string()),
+        };
+        if matches!(command.command, MainCommands::Run(_) | MainCommands::Shell(_)) {
+            bail!("`run` and `shell` cannot be used within a script or shell");
+        }
+        command.command.exec_output()
+    }
+
+    fn variable(&self, name: &str) -> Result<&Output> {
+        self.variables.get(name).ok_or_else(|| anyhow!("Undefined variable: {}", name))
+    }
+
+    /// Replace the variable references in a word with their values.
+    fn substitute(&self, word: &str) -> Result<String> {
+        let mut result = String::new();
+        let mut rest = word;
+        while let Some(index) = rest.find('$') {
+            result.push_str(&rest[..index]);
+            rest = &rest[index + 1..];
+            if let Some(after) = rest.strip_prefix('$') {
+                result.push('$');
+                rest = after;
+                continue;
+            }
+            let (name, after) = if let Some(braced) = rest.strip_prefix('{') {
+                match braced.split_once('}') {
+                    Some(split) => split,
+                    None => bail!("Unterminated variable reference in {}", word),
+                }
+            } else {
+                let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+                rest.split_at(end)
+            };
+            if !is_variable_name(name) {
+                bail!("Invalid variable reference in {}", word);
+            }
+            result.push_str(&self.variable(name)?.to_string());
+            rest = after;
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        pipeline::end();
+    }
+}
+
+/// The envelope in an output, if any. Text that is a single `ur:envelope` or
+/// `ur:xid` is parsed, so commands that print envelopes as text can still be
+/// chained.
+pub fn output_envelope(output: &Output) -> Option<Envelope> {
+    match output {
+        Output::Envelope(envelope) => Some(envelope.clone()),
+        Output::Envelopes(envelopes, _) => match envelopes.as_slice() {
+            [envelope] => Some(envelope.clone()),
+            _ => None,
+        },
+        Output::Text(text) => {
+            let text = text.trim();
+            let is_envelope = (text.starts_with("ur:envelope/") || text.starts_with("ur:xid/"))
+                && !text.contains(char::is_whitespace);
+            if is_envelope { read_envelope(Some(text)).ok() } else { None }
+        }
+    }
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// If the word is exactly `$NAME` or `${NAME}`, returns `NAME`.
+fn variable_reference(word: &str) -> Option<&str> {
+    let name = word.strip_prefix('$')?;
+    let name = name.strip_prefix('{').and_then(|name| name.strip_suffix('}')).unwrap_or(name);
+    is_variable_name(name).then_some(name)
+}
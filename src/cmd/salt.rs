@@ -27,13 +27,9 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
-        Ok((if let Some(size) = self.size {
-            envelope.add_salt_with_len(size)?
-        } else {
-            envelope.add_salt()
-        }).ur_string())
+        crate::api::salt::salt(&envelope, self.size)
     }
 }
@@ -0,0 +1,212 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use clap::{Args, CommandFactory};
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+
+use super::run::{output_envelope, ScriptCommand, Session};
+use crate::utils::read_envelope;
+
+/// The commands of the shell that are not subcommands.
+const BUILTINS: [&str; 6] = ["exit", "help", "show", "undo", "vars", "quit"];
+
+const HELP: &str = "\
+Enter any subcommand as it would be written after `envelope`, for example
+`assertion find predicate string knows`. Subcommands that are not given an
+envelope operate on the current envelope, and subcommands that produce an
+envelope replace it.
+
+  ur:envelope/...       Make the given envelope current.
+  NAME = <subcommand>   Store the output of a subcommand in a variable.
+  $NAME                 Make the envelope in a variable current.
+  show                  Show the current envelope.
+  undo                  Restore the previous current envelope.
+  vars                  List the variables.
+  help                  Show this help.
+  exit, quit            Leave the shell.
+
+Press Tab to complete subcommands, options, values, and variables.";
+
+/// Explore and edit an envelope interactively.
+///
+/// Holds a current envelope and runs subcommands against it, showing the
+/// envelope after each change. Changes can be undone.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Show the current envelope as a tree instead of in envelope notation.
+    #[arg(long)]
+    tree: bool,
+
+    /// The initial current envelope.
+    envelope: Option<String>,
+}
+
+impl crate::exec::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let mut session = Session::new();
+        if let Some(envelope) = &self.envelope {
+            session.set_current_envelope(Some(read_envelope(Some(envelope))?));
+            self.show(&session);
+        }
+
+        let variables = Rc::new(RefCell::new(Vec::new()));
+        let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new()?;
+        editor.set_helper(Some(ShellHelper { command: ScriptCommand::command(), variables: variables.clone() }));
+
+        let mut undo_stack: Vec<Option<Envelope>> = Vec::new();
+        loop {
+            let line = match editor.readline("envelope> ") {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(e.into()),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            editor.add_history_entry(line)?;
+
+            match line {
+                "exit" | "quit" => break,
+                "help" => println!("{}", HELP),
+                "show" => self.show(&session),
+                "vars" => {
+                    for name in session.variable_names() {
+                        println!("{}", name);
+                    }
+                }
+                "undo" => match undo_stack.pop() {
+                    Some(envelope) => {
+                        session.set_current_envelope(envelope);
+                        self.show(&session);
+                    }
+                    None => eprintln!("Nothing to undo"),
+                },
+                _ => {
+                    let before = session.current_envelope();
+                    match self.run_line(&mut session, line) {
+                        Ok(Some(text)) if !text.is_empty() => println!("{}", text),
+                        Ok(_) => {}
+                        Err(e) => eprintln!("Error: {:#}", e),
+                    }
+                    if is_changed(&before, &session.current_envelope()) {
+                        undo_stack.push(before);
+                        self.show(&session);
+                    }
+                    *variables.borrow_mut() = session.variable_names();
+                }
+            }
+        }
+        Ok(String::new())
+    }
+}
+
+impl CommandArgs {
+    /// Run a line, returning any text that should be printed.
+    fn run_line(&self, session: &mut Session, line: &str) -> Result<Option<String>> {
+        if line.starts_with("ur:") && !line.contains(char::is_whitespace) {
+            session.set_current_envelope(Some(read_envelope(Some(line))?));
+            return Ok(None);
+        }
+        Ok(match session.run_line(line)? {
+            Some(output) if output_envelope(&output).is_none() => Some(output.to_string()),
+            _ => None,
+        })
+    }
+
+    fn show(&self, session: &Session) {
+        match session.current_envelope() {
+            Some(envelope) if self.tree => println!("{}", envelope.tree_format(false)),
+            Some(envelope) => println!("{}", envelope.format()),
+            None => println!("No current envelope"),
+        }
+    }
+}
+
+fn is_changed(before: &Option<Envelope>, after: &Option<Envelope>) -> bool {
+    match (before, after) {
+        (Some(before), Some(after)) => before.structural_digest() != after.structural_digest(),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// Completes subcommands, options, and values from the command line
+/// definition, and variable references.
+struct ShellHelper {
+    command: clap::Command,
+    variables: Rc<RefCell<Vec<String>>>,
+}
+
+impl ShellHelper {
+    fn candidates(&self, words: &[&str], prefix: &str) -> Vec<String> {
+        if let Some(prefix) = prefix.strip_prefix('$') {
+            return self.variables.borrow().iter()
+                .filter(|name| name.starts_with(prefix))
+                .map(|name| format!("${}", name))
+                .collect();
+        }
+        // Skip the `NAME =` of an assignment.
+        let words = match words {
+            [_, "=", rest @ ..] => rest,
+            _ => words,
+        };
+
+        let mut command = &self.command;
+        for word in words {
+            if let Some(subcommand) = command.find_subcommand(word) {
+                command = subcommand;
+            }
+        }
+
+        let mut candidates: Vec<String> = Vec::new();
+        let option = words.last()
+            .and_then(|word| word.strip_prefix("--"))
+            .and_then(|long| command.get_arguments().find(|arg| arg.get_long() == Some(long)));
+        if let Some(option) = option {
+            candidates.extend(option.get_possible_values().iter().map(|value| value.get_name().to_string()));
+        } else if prefix.starts_with('-') {
+            candidates.extend(command.get_arguments().filter_map(|arg| arg.get_long()).map(|long| format!("--{}", long)));
+        } else if command.has_subcommands() {
+            candidates.extend(command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()));
+            if words.is_empty() {
+                candidates.extend(BUILTINS.iter().map(|builtin| builtin.to_string()));
+            }
+        } else {
+            candidates.extend(command.get_positionals()
+                .flat_map(|arg| arg.get_possible_values())
+                .map(|value| value.get_name().to_string()));
+        }
+        candidates.retain(|candidate| candidate.starts_with(prefix));
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |index| index + 1);
+        let words = line[..start].split_whitespace().collect::<Vec<_>>();
+        Ok((start, self.candidates(&words, &line[start..])))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
@@ -1,10 +1,19 @@
-use anyhow::{bail, Result};
-use clap::Args;
-use known_values::NOTE;
+use std::path::PathBuf;
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
-use bc_components::{PrivateKeyBase, Signer, SigningOptions, SigningPrivateKey};
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, ValueEnum};
+
+use crate::{
+    api::{sign::{sign_detached, sign_with_metadata, ROLE, SIGNER}, xid::{signing_key, KeySelector}},
+    cmd::xid::utils::read_xid_document_arg,
+    data_types::{parse_data_type_to_envelope, DataType},
+    envelope_args::{EnvelopeArgs, EnvelopeArgsLike},
+};
+use bc_components::{PrivateKeyBase, PublicKeys, Signer, SigningOptions, SigningPrivateKey, XIDProvider, XID};
 use bc_envelope::prelude::*;
+use dcbor::Date;
+use bc_xid::{HasName, Key, XIDDocument};
+use known_values::{DATE, NAME, NOTE};
 
 use super::generate::HashType;
 
@@ -12,17 +21,64 @@ use super::generate::HashType;
 #[derive(Debug, Args)]
 #[group(skip)]
 pub struct CommandArgs {
-    /// The signer to sign the envelope subject with. May be a private key base (ur:prvkeys)
-    /// or a signing private key (ur:signing-private-key).
+    /// The signer to sign the envelope subject with. May be a private key base (ur:prvkeys),
+    /// a signing private key (ur:signing-private-key), or a XID document (ur:xid) that
+    /// includes the private key to sign with.
     ///
     /// Multiple signers may be provided.
     #[arg(long, short)]
     signer: Vec<String>,
 
-    /// An optional note to add to the envelope.
+    /// For XID document signers, the key to sign with: its name or its public keys
+    /// (ur:crypto-pubkeys). The key must hold the `Sign` privilege. If omitted, the
+    /// inception key is used if it can sign, and otherwise the only key that can.
+    #[arg(long)]
+    key: Option<String>,
+
+    /// Record the XID of each XID document signer in its signature's metadata.
+    #[arg(long)]
+    record_xid: bool,
+
+    /// Record the name of the key each XID document signer signed with in its
+    /// signature's metadata.
+    #[arg(long)]
+    record_key_name: bool,
+
+    /// An optional note to add to the signature's metadata. Requires a single
+    /// signer.
     #[arg(long)]
     note: Option<String>,
 
+    /// A signing date (ISO 8601, or `now`) to add to the signature's metadata.
+    /// Requires a single signer.
+    #[arg(long)]
+    date: Option<String>,
+
+    /// The purpose or role of the signature, a known value (name or number)
+    /// added to the signature's metadata. Requires a single signer.
+    #[arg(long)]
+    role: Option<String>,
+
+    /// An assertion to add to the signature's metadata, given as the
+    /// predicate type and value followed by the object type and value.
+    /// Requires a single signer.
+    ///
+    /// Multiple assertions may be provided.
+    #[arg(long = "assertion", num_args = 4, value_names = ["PRED_TYPE", "PRED_VALUE", "OBJ_TYPE", "OBJ_VALUE"])]
+    assertions: Vec<String>,
+
+    /// Produce a detached signature instead of signing the envelope's subject:
+    /// an envelope whose subject is the digest of the whole envelope, with the
+    /// signatures as its assertions.
+    #[arg(long)]
+    detached: bool,
+
+    /// Produce a detached signature for the contents of a file, identified by
+    /// its digest (as computed by `generate digest`), instead of reading an
+    /// envelope. Cannot be combined with an envelope or `--detached`.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["envelope", "detached"])]
+    file: Option<PathBuf>,
+
     /// Namespace for SSH signatures.
     #[arg(long, default_value = "envelope")]
     namespace: String,
@@ -41,15 +97,12 @@ impl EnvelopeArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
-        let envelope = self.read_envelope()?;
-        if self.signer.is_empty() {
-            bail!("at least one signer must be provided");
-        }
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let mut private_key_bases: Vec<PrivateKeyBase> = Vec::new();
         let mut signing_private_keys: Vec<SigningPrivateKey> = Vec::new();
         let mut signing_options: Vec<Option<SigningOptions>> = Vec::new();
+        let mut xid_documents: Vec<XIDDocument> = Vec::new();
         for s in &self.signer {
             if let Ok(key) = PrivateKeyBase::from_ur_string(s) {
                 private_key_bases.push(key);
@@ -65,25 +118,84 @@ impl crate::exec::Exec for CommandArgs {
                     signing_options.push(None);
                 }
                 signing_private_keys.push(key);
+            } else if let Some(xid_document) = read_xid_document_arg(s)? {
+                xid_documents.push(xid_document);
             } else {
                 bail!("invalid signer: {}", s);
             }
         }
+        if self.signer.len() > 1 && (self.note.is_some() || self.date.is_some() || self.role.is_some() || !self.assertions.is_empty()) {
+            bail!("--note, --date, --role, and --assertion can only be used with a single signer; sign once per signer to give each signature its own metadata");
+        }
+        if (self.record_xid || self.record_key_name) && xid_documents.is_empty() {
+            bail!("--record-xid and --record-key-name require a XID document signer");
+        }
+        let selector = self.key.as_deref().map(|key| match PublicKeys::from_ur_string(key) {
+            Ok(public_keys) => KeySelector::Public(public_keys),
+            Err(_) => KeySelector::Name(key.to_string()),
+        });
+        let common_metadata = self.common_metadata()?;
+        let metadata = |xid_key: Option<(XID, &Key)>| {
+            let mut metadata = common_metadata.clone();
+            if let Some((xid, key)) = xid_key {
+                if self.record_xid {
+                    metadata = metadata.with_assertion(SIGNER, xid);
+                }
+                if self.record_key_name && !key.name().is_empty() {
+                    metadata = metadata.with_assertion(NAME, key.name());
+                }
+            }
+            Some(metadata).filter(|metadata| metadata.has_assertions())
+        };
+
         let mut signers: Vec<(&dyn Signer, Option<SigningOptions>, Option<SignatureMetadata>)> = Vec::new();
         for key in private_key_bases.iter() {
-            signers.push((key as &dyn Signer, None, None));
+            signers.push((key as &dyn Signer, None, metadata(None)));
         }
         for i in 0..signing_private_keys.len() {
-            signers.push((&signing_private_keys[i] as &dyn Signer, signing_options[i].clone(), None));
+            signers.push((&signing_private_keys[i] as &dyn Signer, signing_options[i].clone(), metadata(None)));
         }
-        if let Some(note) = &self.note {
-            if signers.len() != 1 {
-                bail!("can only add a note on a single signature");
-            }
-            let metadata = SignatureMetadata::new().with_assertion(NOTE, note.clone());
-            Ok(envelope.add_signature_opt(signers[0].0, signers[0].1.clone(), Some(metadata)).ur_string())
+        for xid_document in xid_documents.iter() {
+            let key = signing_key(xid_document, selector.as_ref())?;
+            let private_keys = key.private_keys().unwrap();
+            signers.push((private_keys as &dyn Signer, None, metadata(Some((xid_document.xid(), key)))));
+        }
+        if let Some(path) = &self.file {
+            return sign_detached(&Digest::from_image(std::fs::read(path)?), &signers);
+        }
+        let envelope = self.read_envelope()?;
+        if self.detached {
+            sign_detached(&envelope.digest(), &signers)
         } else {
-            Ok(envelope.add_signatures_opt(&signers).ur_string())
+            sign_with_metadata(&envelope, &signers)
+        }
+    }
+}
+
+impl CommandArgs {
+    /// The metadata given by `--note`, `--date`, `--role`, and `--assertion`.
+    fn common_metadata(&self) -> Result<SignatureMetadata> {
+        let mut metadata = SignatureMetadata::new();
+        if let Some(note) = &self.note {
+            metadata = metadata.with_assertion(NOTE, note.as_str());
+        }
+        if let Some(date) = &self.date {
+            let date = if date == "now" {
+                Date::from_timestamp(Date::now().timestamp().floor())
+            } else {
+                Date::from_string(date.as_str())?
+            };
+            metadata = metadata.with_assertion(DATE, date);
+        }
+        if let Some(role) = &self.role {
+            metadata = metadata.with_assertion(ROLE, parse_data_type_to_envelope(DataType::Known, Some(role), None)?);
+        }
+        for assertion in self.assertions.chunks(4) {
+            let data_type = |s: &str| DataType::from_str(s, true).map_err(|e| anyhow!("invalid data type: {}", e));
+            let predicate = parse_data_type_to_envelope(data_type(&assertion[0])?, Some(&assertion[1]), None)?;
+            let object = parse_data_type_to_envelope(data_type(&assertion[2])?, Some(&assertion[3]), None)?;
+            metadata = metadata.with_assertion(predicate, object);
         }
+        Ok(metadata)
     }
 }
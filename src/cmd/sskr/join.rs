@@ -1,17 +1,21 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use clap::Args;
 use bc_envelope::prelude::*;
 
+use crate::utils::read_envelope;
+
 /// Join a set of SSKR shares back into the original envelope.
 #[derive(Debug, Args)]
 #[group(skip)]
 pub struct CommandArgs {
     /// The shares to join (ur:envelope).
+    ///
+    /// Shares sealed to a share recipient may be given once decrypted.
     shares: Vec<String>,
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         // If envelopes is empty, read them from stdin, one per line.
         let mut shares = self.shares.clone();
         if shares.is_empty() {
@@ -24,17 +28,9 @@ impl crate::exec::Exec for CommandArgs {
 
         let shares: Vec<Envelope> = shares
             .iter()
-            .map(|s| Envelope::from_ur_string(s).unwrap())
-            .collect();
-
-        // Make sure we have at least one.
-        if shares.is_empty() {
-            bail!("No share envelopes provided");
-        }
+            .map(|s| read_envelope(Some(s)))
+            .collect::<Result<_>>()?;
 
-        let shares_refs: Vec<&Envelope> = shares.iter().collect();
-        let wrapped = bc_envelope::Envelope::sskr_join(&shares_refs)?;
-        let result = wrapped.unwrap_envelope()?;
-        Ok(result.ur_string())
+        crate::api::sskr::join(&shares)
     }
 }
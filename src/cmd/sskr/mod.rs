@@ -25,4 +25,11 @@ impl crate::exec::Exec for CommandArgs {
             SskrCommands::Join(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SskrCommands::Split(args) => args.exec_output(),
+            SskrCommands::Join(args) => args.exec_output(),
+        }
+    }
 }
@@ -1,9 +1,14 @@
 use anyhow::bail;
-use bc_components::{SymmetricKey, SSKRSpec, SSKRGroupSpec, SSKRError, PublicKeys};
+use bc_components::{SymmetricKey, SSKRSpec, SSKRGroupSpec, SSKRError, PublicKeys, XIDProvider};
 use clap::Args;
 pub use anyhow::Result;
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    api::{sskr::ShareRecipient, xid::can_encrypt},
+    cmd::xid::utils::read_xid_document_arg,
+    envelope_args::{EnvelopeArgs, EnvelopeArgsLike},
+    exec::Output,
+};
 use bc_envelope::prelude::*;
 
 /// Split an envelope into several shares using SSKR.
@@ -34,6 +39,18 @@ pub struct CommandArgs {
     #[arg(short = 'r', long = "recipient")]
     recipients: Vec<String>,
 
+    /// A recipient of particular shares, as `SHARE=RECIPIENT`.
+    ///
+    /// `SHARE` is a group number (e.g., `2`) for every share in that group, or a
+    /// group and member number (e.g., `1.2`) for a single share, counting from 1.
+    /// `RECIPIENT` is a public key (ur:crypto-pubkeys), or a XID document (ur:xid)
+    /// whose keys holding the `Encrypt` privilege are each used. Each of its
+    /// shares is wrapped and sealed to the recipient alone, who can decrypt the
+    /// share but not the envelope, and records the recipient in a `'holder'`
+    /// assertion. May be specified multiple times.
+    #[arg(long = "share-recipient", value_name = "SHARE=RECIPIENT")]
+    share_recipients: Vec<String>,
+
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
 }
@@ -46,6 +63,10 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = self.read_envelope()?;
 
         if self.group_threshold > self.groups.len() {
@@ -66,13 +87,18 @@ impl crate::exec::Exec for CommandArgs {
             })
             .collect::<Result<_>>()?;
 
-        let content_key = match &self.key {
-            Some(key) => SymmetricKey::from_ur_string(key)?,
-            None => SymmetricKey::new(),
-        };
+        let content_key = self.key.as_deref().map(SymmetricKey::from_ur_string).transpose()?;
+        let recipients: Vec<PublicKeys> = self
+            .recipients
+            .iter()
+            .map(PublicKeys::from_ur_string)
+            .collect::<Result<_>>()?;
+        let share_recipients = self
+            .share_recipients
+            .iter()
+            .map(|s| parse_share_recipient(s))
+            .collect::<Result<Vec<_>>>()?;
 
-        let wrapped = envelope.wrap_envelope();
-        let encrypted = wrapped.encrypt_subject(&content_key)?;
         let group_spec_results: Vec<Result<SSKRGroupSpec, SSKRError>> = groups
             .iter()
             .map(|(m, n)| SSKRGroupSpec::new(*m, *n))
@@ -81,34 +107,44 @@ impl crate::exec::Exec for CommandArgs {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()?;
         let spec = SSKRSpec::new(self.group_threshold, group_specs)?;
-        let grouped_shares = encrypted.sskr_split(&spec, &content_key)?;
-        let flattened_shares = grouped_shares.into_iter().flatten().collect::<Vec<_>>();
-        let flattened_shares = if self.recipients.is_empty() {
-            flattened_shares
-        } else {
-            let recipients: Vec<PublicKeys> = self
-                .recipients
-                .iter()
-                .map(PublicKeys::from_ur_string)
-                .collect::<Result<_>>()?;
-            flattened_shares
-                .into_iter()
-                .map(|share| {
-                    let mut share = share;
-                    for recipient in &recipients {
-                        share = share.add_recipient(recipient, &content_key);
-                    }
-                    Ok(share)
-                })
-                .collect::<Result<Vec<_>>>()?
-        };
-
-        let output_shares = flattened_shares
-            .iter()
-            .map(|share| share.ur_string())
-            .collect::<Vec<_>>()
-            .join(" ");
+        let grouped_shares = crate::api::sskr::split(&envelope, &spec, content_key.as_ref(), &recipients, &share_recipients)?;
 
-        Ok(output_shares)
+        let output_shares = grouped_shares
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        Ok(Output::Envelopes(output_shares, " "))
     }
 }
+
+fn parse_share_recipient(s: &str) -> Result<ShareRecipient> {
+    let (share, recipient) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Invalid share recipient, expected SHARE=RECIPIENT: {}", s))?;
+    let index = |n: &str| match n.parse::<usize>() {
+        Ok(n) if n >= 1 => Ok(n - 1),
+        _ => bail!("Invalid share specifier: {}", share),
+    };
+    let (group, member) = match share.split_once('.') {
+        Some((group, member)) => (index(group)?, Some(index(member)?)),
+        None => (index(share)?, None),
+    };
+    let (holder, public_keys) = if let Ok(public_keys) = PublicKeys::from_ur_string(recipient) {
+        (Envelope::new(public_keys.clone()), vec![public_keys])
+    } else if let Some(xid_document) = read_xid_document_arg(recipient)? {
+        let public_keys: Vec<PublicKeys> = xid_document
+            .keys()
+            .iter()
+            .filter(|key| can_encrypt(key))
+            .map(|key| key.public_keys().clone())
+            .collect();
+        if public_keys.is_empty() {
+            bail!("XID document share recipient has no keys with the Encrypt privilege");
+        }
+        (Envelope::new(xid_document.xid()), public_keys)
+    } else {
+        bail!("Invalid share recipient: {}", recipient);
+    };
+    Ok(ShareRecipient { group, member, holder, public_keys })
+}
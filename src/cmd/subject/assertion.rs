@@ -33,8 +33,8 @@ impl PredObjArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
-        Ok(self.assertion_envelope()?.ur_string())
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        self.assertion_envelope()
     }
 }
@@ -0,0 +1,33 @@
+use bc_envelope::prelude::*;
+use clap::Args;
+use anyhow::Result;
+
+use crate::{document::{document_to_envelope, parse_document, DocumentFormat}, utils::read_argument};
+
+/// Create an envelope from a JSON or YAML document.
+///
+/// Each key of an object becomes an assertion. Keys in single quotes (e.g.
+/// `'isA'`) are known values. Arrays become repeated assertions with the same
+/// predicate. Nested objects become wrapped envelopes, and the `@subject` key
+/// sets the subject of an object's envelope.
+///
+/// Values that are not strings, numbers, booleans, or null may be written as
+/// typed values: `{"@type": "date", "@value": "2022-08-30"}`, where `@type` is
+/// any data type accepted by `subject type`.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The syntax of the document.
+    #[arg(long, default_value = "json")]
+    format: DocumentFormat,
+
+    /// The JSON or YAML document. If not provided, it is read from stdin.
+    document: Option<String>,
+}
+
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        let document = parse_document(&read_argument(self.document.as_deref())?, self.format)?;
+        document_to_envelope(&document)
+    }
+}
@@ -1,5 +1,6 @@
 pub mod r#type;
 pub mod assertion;
+pub mod json;
 
 use clap::{Subcommand, Args};
 use anyhow::Result;
@@ -16,6 +17,7 @@ pub struct CommandArgs {
 enum SubjectCommands {
     Type(r#type::CommandArgs),
     Assertion(assertion::CommandArgs),
+    Json(json::CommandArgs),
 }
 
 impl crate::exec::Exec for CommandArgs {
@@ -23,6 +25,15 @@ impl crate::exec::Exec for CommandArgs {
         match &self.command {
             SubjectCommands::Type(args) => args.exec(),
             SubjectCommands::Assertion(args) => args.exec(),
+            SubjectCommands::Json(args) => args.exec(),
+        }
+    }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubjectCommands::Type(args) => args.exec_output(),
+            SubjectCommands::Assertion(args) => args.exec_output(),
+            SubjectCommands::Json(args) => args.exec_output(),
         }
     }
 }
@@ -26,8 +26,8 @@ impl SubjectArgsLike for CommandArgs {
     }
 }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
-        Ok(parse_data_type_to_envelope(self.subject_type(), Some(self.read_subject_value()?).as_deref(), self.ur_tag())?.ur_string())
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
+        parse_data_type_to_envelope(self.subject_type(), Some(self.read_subject_value()?).as_deref(), self.ur_tag())
     }
 }
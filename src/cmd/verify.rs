@@ -1,7 +1,15 @@
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
 use clap::Args;
 
-use crate::envelope_args::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    api::{sign::{detached_target, verified_metadata, verify}, xid::XIDVerifier},
+    cmd::xid::utils::read_xid_document_arg,
+    envelope_args::{EnvelopeArgs, EnvelopeArgsLike},
+    exec::Output,
+    utils::parse_envelope_ur,
+};
 use bc_components::{PrivateKeyBase, PublicKeys, SigningPrivateKey, SigningPublicKey, Verifier};
 use bc_envelope::prelude::*;
 
@@ -16,18 +24,41 @@ pub struct CommandArgs {
     #[arg(long, short, default_value = "false")]
     silent: bool,
 
+    /// A detached signature (ur:envelope) to verify against the digest of the
+    /// whole envelope, instead of the signatures on the envelope's subject.
+    #[arg(long, value_name = "SIGNATURE")]
+    detached: Option<String>,
+
+    /// Verify the detached signature against the contents of a file instead
+    /// of an envelope. On success, the detached signature is printed. Cannot be
+    /// combined with an envelope.
+    #[arg(long, value_name = "PATH", requires = "detached", conflicts_with = "envelope")]
+    file: Option<PathBuf>,
+
+    /// On success, print the metadata of each valid signature instead of the
+    /// envelope's UR.
+    #[arg(long, default_value = "false")]
+    metadata: bool,
+
     /// The minimum number of required valid signatures.
     #[arg(long, short, default_value = "1")]
     threshold: usize,
 
     /// The verifier(s). May be a private key base (ur:prvkeys), `PublicKeys`
-    /// (ur:pubkeys) signing private key (ur:signing-private-key), or a signing
-    /// public key (ur:signing-public-key).
+    /// (ur:pubkeys) signing private key (ur:signing-private-key), a signing
+    /// public key (ur:signing-public-key), or a XID document (ur:xid), which
+    /// accepts a signature by any of its keys holding the `Sign` privilege and
+    /// counts once toward the threshold.
     ///
     /// Multiple verifiers may be provided.
     #[arg(long, short)]
     verifier: Vec<String>,
 
+    /// For XID document verifiers, also accept signatures from the signing
+    /// keys of delegates that hold the `Sign` privilege.
+    #[arg(long)]
+    follow_delegates: bool,
+
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
 }
@@ -40,14 +71,31 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let envelope = self.read_envelope()?;
-        if self.verifier.is_empty() {
-            bail!("at least one verifier must be provided");
-        }
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
+        let (envelope, target) = match (&self.detached, &self.file) {
+            (Some(signature), Some(path)) => {
+                let signature = parse_envelope_ur(signature)?;
+                let target = detached_target(&Digest::from_image(std::fs::read(path)?), &signature)?;
+                (signature, target)
+            }
+            (Some(signature), None) => {
+                let envelope = self.read_envelope()?;
+                let target = detached_target(&envelope.digest(), &parse_envelope_ur(signature)?)?;
+                (envelope, target)
+            }
+            _ => {
+                let envelope = self.read_envelope()?;
+                (envelope.clone(), envelope)
+            }
+        };
         let mut private_key_bases: Vec<PrivateKeyBase> = Vec::new();
         let mut public_keys_vec: Vec<PublicKeys> = Vec::new();
         let mut signing_private_keys: Vec<SigningPrivateKey> = Vec::new();
         let mut signing_public_keys: Vec<SigningPublicKey> = Vec::new();
+        let mut xid_verifiers: Vec<XIDVerifier> = Vec::new();
         for v in &self.verifier {
             if let Ok(key) = PrivateKeyBase::from_ur_string(v) {
                 private_key_bases.push(key);
@@ -57,6 +105,12 @@ impl crate::exec::Exec for CommandArgs {
                 signing_private_keys.push(key);
             } else if let Ok(key) = SigningPublicKey::from_ur_string(v) {
                 signing_public_keys.push(key);
+            } else if let Some(xid_document) = read_xid_document_arg(v)? {
+                let verifier = XIDVerifier::new(&xid_document, self.follow_delegates);
+                if verifier.keys().is_empty() {
+                    bail!("XID document verifier has no keys with the Sign privilege");
+                }
+                xid_verifiers.push(verifier);
             } else {
                 bail!("invalid verifier: {}", v);
             }
@@ -74,7 +128,14 @@ impl crate::exec::Exec for CommandArgs {
         for key in signing_public_keys.iter() {
             verifiers.push(key as &dyn Verifier);
         }
-        envelope.clone().verify_signatures_from_threshold(&verifiers, Some(self.threshold))?;
-        Ok(if self.silent { "".to_string() } else { envelope.ur_string() })
+        for verifier in xid_verifiers.iter() {
+            verifiers.push(verifier as &dyn Verifier);
+        }
+        verify(&target, &verifiers, self.threshold)?;
+        if self.metadata {
+            let metadata = verified_metadata(&target, &verifiers)?;
+            return Ok(Output::Text(metadata.iter().map(|metadata| metadata.format()).collect::<Vec<_>>().join("\n")));
+        }
+        Ok(if self.silent { Output::Text("".to_string()) } else { Output::Envelope(envelope) })
     }
 }
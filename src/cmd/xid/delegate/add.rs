@@ -1,14 +1,13 @@
 use bc_ur::prelude::*;
-use bc_xid::{Delegate, XIDDocument};
+use bc_xid::XIDDocument;
 use clap::Args;
-use anyhow::{bail, Result};
+use anyhow::Result;
 
 use crate::{
     cmd::xid::{xid_privilege::XIDPrivilege, utils::XIDDocumentReadable},
     envelope_args::{ EnvelopeArgs, EnvelopeArgsLike },
 };
 
-use super::add_delegate_permissions;
 use super::xid_document_to_unsigned_envelope_ur_string;
 
 /// Add a delegate to the XID document.
@@ -36,17 +35,11 @@ impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let delegate_xid = XIDDocument::from_ur_string(&self.delegate)?;
-        let mut delegate = Delegate::new(delegate_xid);
-
-        if self.permissions.is_empty() {
-            bail!("At least one permission must be granted to the delegate.");
-        }
-
-        add_delegate_permissions(&mut delegate, &self.permissions);
+        let delegate = XIDDocument::from_ur_string(&self.delegate)?;
+        let permissions = self.permissions.iter().map(|&privilege| privilege.into()).collect::<Vec<_>>();
 
         let mut xid_document = self.read_xid_document()?;
-        xid_document.add_delegate(delegate)?;
+        crate::api::xid::add_delegate(&mut xid_document, delegate, &permissions)?;
 
         Ok(xid_document_to_unsigned_envelope_ur_string(xid_document))
     }
@@ -2,11 +2,11 @@ use bc_envelope::known_values;
 use bc_xid::XIDDocument;
 use clap::Args;
 use anyhow::Result;
-use bc_ur::prelude::*;
 
 use crate::{
     cmd::xid::utils::XIDDocumentReadable,
     envelope_args::{ EnvelopeArgs, EnvelopeArgsLike },
+    exec::Output,
 };
 
 /// Retrieve all delegates from the XID document.
@@ -27,14 +27,17 @@ impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = self.read_envelope()?;
         XIDDocument::from_unsigned_envelope(&envelope)?; // Validation only
         let delegate_assertions = envelope.assertions_with_predicate(known_values::DELEGATE);
         let delegates = delegate_assertions
             .iter()
-            .map(|delegate| delegate.try_object().unwrap().ur_string())
-            .collect::<Vec<_>>()
-            .join("\n");
-        Ok(delegates)
+            .map(|delegate| delegate.try_object().unwrap())
+            .collect::<Vec<_>>();
+        Ok(Output::Envelopes(delegates, "\n"))
     }
 }
@@ -1,5 +1,4 @@
-use bc_envelope::known_values;
-use bc_ur::prelude::*;
+use bc_envelope::{known_values, Envelope};
 use bc_xid::XIDDocument;
 use clap::Args;
 use anyhow::{Result, anyhow};
@@ -25,13 +24,12 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl XIDDocumentReadable for CommandArgs { }
 
-impl crate::exec::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
+impl crate::exec::ExecEnvelope for CommandArgs {
+    fn exec_envelope(&self) -> Result<Envelope> {
         let envelope = self.read_envelope()?;
         XIDDocument::from_unsigned_envelope(&envelope)?; // Validation only
         let delegate_assertions = envelope.assertions_with_predicate(known_values::DELEGATE);
         let delegate_assertion = delegate_assertions.get(self.index).ok_or_else(|| anyhow!("Index out of bounds"))?;
-        let delegate = delegate_assertion.try_object()?;
-        Ok(delegate.ur_string())
+        delegate_assertion.try_object()
     }
 }
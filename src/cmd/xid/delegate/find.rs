@@ -4,7 +4,7 @@ use bc_xid::XIDDocument;
 use clap::Args;
 use anyhow::Result;
 
-use crate::{cmd::xid::utils::XIDDocumentReadable, envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }};
+use crate::{cmd::xid::utils::XIDDocumentReadable, envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }, exec::Output};
 
 /// Find a delegate in the XID document.
 #[derive(Debug, Args)]
@@ -27,12 +27,13 @@ impl XIDDocumentReadable for CommandArgs { }
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let target_xid_document = XIDDocument::from_ur_string(self.delegate.as_str())?;
         let xid_document = self.read_xid_document()?;
-        if let Some(delegate) = xid_document.find_delegate_by_xid(&target_xid_document) {
-            Ok(delegate.to_envelope().ur_string())
-        } else {
-            Ok("".to_string())
-        }
+        let result = xid_document.find_delegate_by_xid(&target_xid_document).map(|delegate| delegate.to_envelope()).into_iter().collect();
+        Ok(Output::Envelopes(result, "\n"))
     }
 }
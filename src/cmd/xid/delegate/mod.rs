@@ -7,12 +7,10 @@ pub mod remove;
 pub mod update;
 
 use bc_ur::prelude::*;
-use bc_xid::{Delegate, HasPermissions, Privilege, XIDDocument};
+use bc_xid::XIDDocument;
 use clap::{Subcommand, Args};
 use anyhow::Result;
 
-use super::xid_privilege::XIDPrivilege;
-
 /// Work with a XID document's keys.
 #[derive(Debug, Args)]
 #[group(skip)]
@@ -44,16 +42,16 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Update(args) => args.exec(),
         }
     }
-}
 
-fn add_delegate_permissions(delegate: &mut Delegate, permissions: &[XIDPrivilege]) {
-    // If `All` is in the permissions, just add it.
-    if permissions.contains(&XIDPrivilege::All) {
-        delegate.add_allow(Privilege::All);
-    } else {
-        // Otherwise, add each permission.
-        for permission in permissions {
-            delegate.add_allow((*permission).into());
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Add(args) => args.exec_output(),
+            SubCommands::All(args) => args.exec_output(),
+            SubCommands::At(args) => args.exec_output(),
+            SubCommands::Count(args) => args.exec_output(),
+            SubCommands::Find(args) => args.exec_output(),
+            SubCommands::Remove(args) => args.exec_output(),
+            SubCommands::Update(args) => args.exec_output(),
         }
     }
 }
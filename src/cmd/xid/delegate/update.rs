@@ -1,6 +1,6 @@
-use bc_xid::{Delegate, XIDDocument};
+use bc_xid::XIDDocument;
 use clap::Args;
-use anyhow::{Result, bail};
+use anyhow::Result;
 use bc_ur::prelude::*;
 
 use crate::{
@@ -8,7 +8,7 @@ use crate::{
     envelope_args::{ EnvelopeArgs, EnvelopeArgsLike },
 };
 
-use super::{add_delegate_permissions, xid_document_to_unsigned_envelope_ur_string};
+use super::xid_document_to_unsigned_envelope_ur_string;
 
 /// Update a delegate in the XID document.
 #[derive(Debug, Args)]
@@ -36,16 +36,10 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let replacement_xid_document = XIDDocument::from_ur_string(self.delegate.as_str())?;
-        let mut delegate = Delegate::new(&replacement_xid_document);
+        let permissions = self.permissions.iter().map(|&privilege| privilege.into()).collect::<Vec<_>>();
 
         let mut xid_document = self.read_xid_document()?;
-        xid_document.take_delegate(&delegate);
-        if self.permissions.is_empty() {
-            bail!("At least one permission must be granted to the delegate.");
-        }
-
-        add_delegate_permissions(&mut delegate, &self.permissions);
-        xid_document.add_delegate(delegate)?;
+        crate::api::xid::update_delegate(&mut xid_document, replacement_xid_document, &permissions)?;
 
         Ok(xid_document_to_unsigned_envelope_ur_string(xid_document))
     }
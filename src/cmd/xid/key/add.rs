@@ -1,11 +1,11 @@
 use bc_components::URI;
-use bc_xid::{ Key, PrivateKeyOptions };
+use bc_xid::PrivateKeyOptions;
 use clap::Args;
 use anyhow::Result ;
 
 use crate::{
     cmd::xid::{
-        key_args::{ KeyArgs, KeyArgsLike }, private_options::PrivateOptions, utils::{envelope_to_xid_ur_string, update_key, InputKey, XIDDocumentReadable}, xid_privilege::XIDPrivilege
+        key_args::{ KeyArgs, KeyArgsLike }, private_options::PrivateOptions, utils::{envelope_to_xid_ur_string, XIDDocumentReadable}, xid_privilege::XIDPrivilege
     },
     envelope_args::{ EnvelopeArgs, EnvelopeArgsLike },
 };
@@ -57,18 +57,7 @@ impl crate::exec::Exec for CommandArgs {
 
         let mut xid_document = self.read_xid_document()?;
 
-        let mut key = match &keys {
-            InputKey::Private(private_key_base) => {
-                Key::new_with_private_key_base(private_key_base.clone())
-            }
-            InputKey::Public(public_keys) => {
-                Key::new(public_keys.clone())
-            }
-        };
-
-        update_key(&mut key, self.name(), self.endpoints(), self.permissions());
-
-        xid_document.add_key(key)?;
+        crate::api::xid::add_key(&mut xid_document, &keys, &self.key_options())?;
 
         let options = PrivateKeyOptions::from(self.private_opts());
         let unsigned_envelope = xid_document.to_unsigned_envelope_opt(options);
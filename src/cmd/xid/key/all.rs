@@ -1,10 +1,9 @@
 use bc_envelope::known_values;
-use bc_ur::prelude::*;
 use bc_xid::XIDDocument;
 use clap::Args;
 use anyhow::Result;
 
-use crate::envelope_args::{ EnvelopeArgs, EnvelopeArgsLike };
+use crate::{envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }, exec::Output};
 
 /// Retrieve all the XID document's keys.
 #[derive(Debug, Args)]
@@ -22,14 +21,17 @@ impl EnvelopeArgsLike for CommandArgs {
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let envelope = self.read_envelope()?;
         XIDDocument::from_unsigned_envelope(&envelope)?; // Validation only
         let key_assertions = envelope.assertions_with_predicate(known_values::KEY);
         let keys = key_assertions
             .iter()
-            .map(|key| key.try_object().unwrap().ur_string())
-            .collect::<Vec<String>>()
-            .join("\n");
-        Ok(keys)
+            .map(|key| key.try_object().unwrap())
+            .collect::<Vec<_>>();
+        Ok(Output::Envelopes(keys, "\n"))
     }
 }
@@ -1,9 +1,8 @@
 use bc_envelope::EnvelopeEncodable;
-use bc_ur::prelude::*;
 use clap::Args;
 use anyhow::Result;
 
-use crate::{cmd::xid::utils::XIDDocumentReadable, envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }};
+use crate::{cmd::xid::utils::XIDDocumentReadable, envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }, exec::Output};
 
 /// Find the XID document's inception key, if it exists.
 #[derive(Debug, Args)]
@@ -23,12 +22,12 @@ impl XIDDocumentReadable for CommandArgs { }
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let xid_document = self.read_xid_document()?;
-        let result = if let Some(inception_key) = xid_document.inception_key() {
-            inception_key.to_envelope().ur_string()
-        } else {
-            "".to_string()
-        };
-        Ok(result)
+        let result = xid_document.inception_key().map(|key| key.to_envelope()).into_iter().collect();
+        Ok(Output::Envelopes(result, "\n"))
     }
 }
@@ -28,4 +28,12 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Public(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Inception(args) => args.exec_output(),
+            SubCommands::Name(args) => args.exec_output(),
+            SubCommands::Public(args) => args.exec_output(),
+        }
+    }
 }
@@ -1,9 +1,8 @@
 use bc_envelope::EnvelopeEncodable;
-use bc_ur::prelude::*;
 use clap::Args;
 use anyhow::Result;
 
-use crate::{cmd::xid::utils::{read_public_key, XIDDocumentReadable}, envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }};
+use crate::{cmd::xid::utils::{read_public_key, XIDDocumentReadable}, envelope_args::{ EnvelopeArgs, EnvelopeArgsLike }, exec::Output};
 
 /// Find the XID document's keys by their public key.
 #[derive(Debug, Args)]
@@ -27,17 +26,21 @@ impl XIDDocumentReadable for CommandArgs { }
 
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        Ok(self.exec_output()?.to_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
         let public_keys = read_public_key(self.keys.as_deref())?;
         let xid_document = self.read_xid_document()?;
 
         let keys = xid_document.keys();
         let result = keys.iter().filter_map(|key| {
             if key.public_keys() == &public_keys {
-                Some(key.to_envelope().ur_string())
+                Some(key.to_envelope())
             } else {
                 None
             }
-        }).collect::<Vec<String>>().join("\n");
-        Ok(result)
+        }).collect::<Vec<_>>();
+        Ok(Output::Envelopes(result, "\n"))
     }
 }
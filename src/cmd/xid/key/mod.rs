@@ -40,4 +40,16 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Update(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Add(args) => args.exec_output(),
+            SubCommands::All(args) => args.exec_output(),
+            SubCommands::At(args) => args.exec_output(),
+            SubCommands::Count(args) => args.exec_output(),
+            SubCommands::Find(args) => args.exec_output(),
+            SubCommands::Remove(args) => args.exec_output(),
+            SubCommands::Update(args) => args.exec_output(),
+        }
+    }
 }
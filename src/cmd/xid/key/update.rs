@@ -1,10 +1,10 @@
 use bc_components::URI;
 use clap::Args;
-use anyhow::{ Result, anyhow };
+use anyhow::Result;
 
 use crate::{
     cmd::xid::{
-        key_args::{ KeyArgs, KeyArgsLike }, private_options::PrivateOptions, utils::{ update_key, xid_document_to_ur_string, XIDDocumentReadable}, xid_privilege::XIDPrivilege
+        key_args::{ KeyArgs, KeyArgsLike }, private_options::PrivateOptions, utils::{ xid_document_to_ur_string, XIDDocumentReadable}, xid_privilege::XIDPrivilege
     },
     envelope_args::{ EnvelopeArgs, EnvelopeArgsLike },
 };
@@ -56,14 +56,7 @@ impl crate::exec::Exec for CommandArgs {
 
         let mut xid_document = self.read_xid_document()?;
 
-        let mut key = xid_document
-            .find_key_by_public_keys(&public_keys)
-            .cloned()
-            .ok_or_else(|| anyhow!("Key not found"))?;
-
-        xid_document.take_key(&key);
-        update_key(&mut key, self.name(), self.endpoints(), self.permissions());
-        xid_document.add_key(key)?;
+        crate::api::xid::update_key(&mut xid_document, &public_keys, &self.key_options())?;
 
         Ok(xid_document_to_ur_string(&xid_document, self.private_opts()))
     }
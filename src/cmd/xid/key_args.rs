@@ -3,6 +3,8 @@ use bc_envelope::PublicKeys;
 use clap::Args;
 use anyhow::Result;
 
+use crate::api::xid::KeyOptions;
+
 use super::{xid_privilege::XIDPrivilege, private_options::PrivateOptions, utils::{read_key, read_public_key, InputKey}};
 
 pub trait KeyArgsLike {
@@ -19,6 +21,14 @@ pub trait KeyArgsLike {
     fn read_public_key(&self) -> Result<PublicKeys> {
         read_public_key(self.keys())
     }
+
+    fn key_options(&self) -> KeyOptions {
+        KeyOptions {
+            name: self.name().to_string(),
+            endpoints: self.endpoints().to_vec(),
+            permissions: self.permissions().iter().map(|&privilege| privilege.into()).collect(),
+        }
+    }
 }
 
 #[derive(Debug, Args)]
@@ -41,4 +41,15 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Service(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::New(args) => args.exec_output(),
+            SubCommands::Id(args) => args.exec_output(),
+            SubCommands::Key(args) => args.exec_output(),
+            SubCommands::Method(args) => args.exec_output(),
+            SubCommands::Delegate(args) => args.exec_output(),
+            SubCommands::Service(args) => args.exec_output(),
+        }
+    }
 }
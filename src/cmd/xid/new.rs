@@ -1,10 +1,9 @@
 use bc_components::URI;
-use bc_xid::XIDDocument;
 use clap::Args;
 use anyhow::Result;
 
 use super::{
-    key_args::{ KeyArgs, KeyArgsLike }, private_options::PrivateOptions, utils::{update_key, xid_document_to_ur_string, InputKey}, xid_privilege::XIDPrivilege
+    key_args::{ KeyArgs, KeyArgsLike }, private_options::PrivateOptions, utils::xid_document_to_ur_string, xid_privilege::XIDPrivilege
 };
 
 /// Create a new XID document from an inception key
@@ -40,20 +39,7 @@ impl KeyArgsLike for CommandArgs {
 impl crate::exec::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let keys = self.read_key()?;
-
-        let mut xid_document = match &keys {
-            InputKey::Private(private_key_base) => {
-                XIDDocument::new_with_private_key_base(private_key_base.clone())
-            }
-            InputKey::Public(public_keys) => {
-                XIDDocument::new(public_keys.clone())
-            }
-        };
-
-        let mut key = xid_document.keys().iter().next().unwrap().clone();
-        xid_document.take_key(&key);
-        update_key(&mut key, self.name(), self.endpoints(), self.permissions());
-        xid_document.add_key(key)?;
+        let xid_document = crate::api::xid::new_document(&keys, &self.key_options())?;
 
         Ok(xid_document_to_ur_string(&xid_document, self.private_opts()))
     }
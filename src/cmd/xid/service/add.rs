@@ -1,5 +1,4 @@
 use bc_components::URI;
-use bc_xid::{ HasName, HasPermissions, Service };
 use clap::Args;
 use anyhow::Result ;
 
@@ -63,36 +62,7 @@ impl crate::exec::Exec for CommandArgs {
 
         let mut xid_document = self.read_xid_document()?;
 
-        let mut service = Service::new(uri);
-
-        if let Some(name) = self.name() {
-            if !name.is_empty() {
-                service.set_name(name);
-            }
-        }
-
-        if let Some(capability) = self.capability() {
-            if !capability.is_empty() {
-                service.set_capability(capability);
-            }
-        }
-
-        for privilege in self.permissions() {
-            service.add_allow((*privilege).into());
-        }
-
-        for key in self.keys() {
-            xid_document.check_contains_key(key)?;
-            service.add_key(key)?;
-        }
-
-        for delegate in self.delegates() {
-            xid_document.check_contains_delegate(delegate)?;
-            service.add_delegate(delegate)?;
-        }
-
-        xid_document.check_service_consistency(&service)?;
-        xid_document.add_service(service)?;
+        crate::api::xid::add_service(&mut xid_document, uri, &self.service_options())?;
 
         Ok(xid_document_to_ur_string(&xid_document, PrivateOptions::Include))
     }
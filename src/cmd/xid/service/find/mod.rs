@@ -25,4 +25,11 @@ impl crate::exec::Exec for CommandArgs {
             SubCommands::Name(args) => args.exec(),
         }
     }
+
+    fn exec_output(&self) -> Result<crate::exec::Output> {
+        match &self.command {
+            SubCommands::Uri(args) => args.exec_output(),
+            SubCommands::Name(args) => args.exec_output(),
+        }
+    }
 }
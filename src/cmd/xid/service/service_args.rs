@@ -5,7 +5,7 @@ use bc_xid::XIDDocument;
 use anyhow::Result;
 use clap::Args;
 
-use crate::cmd::xid::{utils::read_uri, xid_privilege::XIDPrivilege};
+use crate::{api::xid::ServiceOptions, cmd::xid::{utils::read_uri, xid_privilege::XIDPrivilege}};
 
 pub trait ServiceArgsLike {
     fn uri(&self) -> Option<&URI>;
@@ -18,6 +18,16 @@ pub trait ServiceArgsLike {
     fn read_uri(&self) -> Result<URI> {
         read_uri(self.uri())
     }
+
+    fn service_options(&self) -> ServiceOptions {
+        ServiceOptions {
+            name: self.name().map(|name| name.to_string()),
+            capability: self.capability().map(|capability| capability.to_string()),
+            permissions: self.permissions().iter().map(|&privilege| privilege.into()).collect(),
+            keys: self.keys().to_vec(),
+            delegates: self.delegates().to_vec(),
+        }
+    }
 }
 
 fn parse_public_keys(s: &str) -> Result<PublicKeys, String> {
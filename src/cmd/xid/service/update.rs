@@ -1,7 +1,6 @@
 use bc_components::URI;
-use bc_xid::{HasName, HasPermissions};
 use clap::Args;
-use anyhow::{ Result, anyhow };
+use anyhow::Result;
 
 use crate::{
     cmd::xid::{
@@ -63,50 +62,7 @@ impl crate::exec::Exec for CommandArgs {
 
         let mut xid_document = self.read_xid_document()?;
 
-        let mut service = xid_document
-            .find_service_by_uri(&uri)
-            .cloned()
-            .ok_or_else(|| anyhow!("Service not found"))?;
-
-        xid_document.take_service(&uri);
-
-        if let Some(name) = self.name() {
-            if !name.is_empty() {
-                service.set_name(name);
-            }
-        }
-
-        if let Some(capability) = self.capability() {
-            if !capability.is_empty() {
-                service.set_capability(capability);
-            }
-        }
-
-        if !self.permissions().is_empty() {
-            service.clear_all_permissions();
-            for privilege in self.permissions() {
-                service.add_allow((*privilege).into());
-            }
-        }
-
-        if !self.keys().is_empty() {
-            service.key_referenecs_mut().clear();
-            for key in self.keys() {
-                xid_document.check_contains_key(key)?;
-                service.add_key(key)?;
-            }
-        }
-
-        if !self.delegates().is_empty() {
-            service.delegate_references_mut().clear();
-            for delegate in self.delegates() {
-                xid_document.check_contains_delegate(delegate)?;
-                service.add_delegate(delegate)?;
-            }
-        }
-
-        xid_document.check_service_consistency(&service)?;
-        xid_document.add_service(service)?;
+        crate::api::xid::update_service(&mut xid_document, &uri, &self.service_options())?;
 
         Ok(xid_document_to_ur_string(&xid_document, PrivateOptions::Include))
     }
@@ -1,20 +1,16 @@
-use bc_components::URI;
+use bc_components::{ URI, XID };
 use bc_envelope::{ Envelope, PrivateKeyBase, PublicKeys };
 use bc_ur::prelude::*;
 
-use anyhow::{ Result, bail };
-use bc_xid::{ HasName, HasPermissions, Key, PrivateKeyOptions, XIDDocument };
+use anyhow::{ Result, anyhow, bail };
+use bc_xid::{ PrivateKeyOptions, XIDDocument };
 
-use crate::envelope_args::EnvelopeArgsLike;
+pub use crate::api::xid::InputKey;
+use crate::{ api::xid::read_document, envelope_args::EnvelopeArgsLike, utils::parse_envelope_ur };
 
-use super::{private_options::PrivateOptions, xid_privilege::XIDPrivilege};
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum InputKey {
-    Public(PublicKeys),
-    Private(PrivateKeyBase),
-}
+use super::private_options::PrivateOptions;
 
+#[allow(clippy::unnecessary_unwrap)]
 pub fn read_key(key: Option<&str>) -> Result<InputKey> {
     let mut key_string = String::new();
     if key.is_none() {
@@ -43,32 +39,31 @@ pub fn read_public_key(key: Option<&str>) -> Result<PublicKeys> {
     }
 }
 
-pub fn update_key(key: &mut Key, name: &str, endpoints: &[URI], permissions: &[XIDPrivilege]) {
-    if !name.is_empty() {
-        key.set_name(name);
-    }
-
-    if !endpoints.is_empty() {
-        for uri in endpoints {
-            key.add_endpoint(uri.clone());
-        }
+/// Read a XID document given as a signer, verifier, or recipient. Returns
+/// `None` if the argument is not a XID document, and an error if it is one
+/// that cannot be read.
+pub fn read_xid_document_arg(s: &str) -> Result<Option<XIDDocument>> {
+    let Ok(envelope) = parse_envelope_ur(s) else {
+        return Ok(None);
+    };
+    let mut subject = envelope.subject();
+    while let Ok(wrapped) = subject.unwrap_envelope() {
+        subject = wrapped.subject();
     }
-
-    if !permissions.is_empty() {
-        key.clear_all_permissions();
-        for privilege in permissions {
-            key.add_permission((*privilege).into());
-        }
+    if subject.extract_subject::<XID>().is_err() {
+        return Ok(None);
     }
+    read_document(&envelope).map(Some).map_err(|e| anyhow!("invalid XID document: {}", e))
 }
 
 pub trait XIDDocumentReadable: EnvelopeArgsLike {
     fn read_xid_document(&self) -> Result<XIDDocument> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_unsigned_envelope(&envelope)
+        read_document(&envelope)
     }
 }
 
+#[allow(clippy::unnecessary_unwrap)]
 pub fn read_uri(uri: Option<&URI>) -> Result<URI> {
     let mut uri_string = String::new();
     if uri.is_none() {
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use bc_components::{Digest, ARID, URI, UUID};
+use bc_envelope::prelude::*;
+use clap::ValueEnum;
+use dcbor::Simple;
+use serde_json::{json, Map, Value};
+
+use crate::data_types::{parse_data_type_to_envelope, DataType};
+
+/// The key of a document object that holds the subject of the envelope.
+const SUBJECT_KEY: &str = "@subject";
+/// The key of a typed value that holds its `DataType`.
+const TYPE_KEY: &str = "@type";
+/// The key of a typed value that holds its string representation.
+const VALUE_KEY: &str = "@value";
+/// The key of a typed value that holds the CBOR tag for an enclosed UR.
+const TAG_KEY: &str = "@tag";
+
+/// The syntax of a document.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DocumentFormat {
+    /// JSON
+    Json,
+    /// YAML
+    Yaml,
+}
+
+/// Parse a JSON or YAML document.
+pub fn parse_document(s: &str, format: DocumentFormat) -> Result<Value> {
+    match format {
+        DocumentFormat::Json => serde_json::from_str(s).map_err(|e| anyhow!("Invalid JSON document: {}", e)),
+        DocumentFormat::Yaml => serde_yaml::from_str(s).map_err(|e| anyhow!("Invalid YAML document: {}", e)),
+    }
+}
+
+/// Build an envelope from a JSON document.
+///
+/// - Objects become envelopes whose subject is the value of the `@subject`
+///   key (`null` if absent), with one assertion per remaining key.
+/// - Keys in single quotes (e.g. `'isA'`) become known value predicates.
+/// - Arrays become one assertion per element, all with the same predicate.
+/// - Objects nested within other objects become wrapped envelopes.
+/// - Objects with `@type` and `@value` keys become a value of the given
+///   `DataType`.
+pub fn document_to_envelope(document: &Value) -> Result<Envelope> {
+    match document {
+        Value::Object(object) if !is_typed_value(object) => object_to_envelope(object),
+        _ => value_to_envelope(document),
+    }
+}
+
+fn object_to_envelope(object: &Map<String, Value>) -> Result<Envelope> {
+    let mut envelope = match object.get(SUBJECT_KEY) {
+        Some(subject) => value_to_envelope(subject)?,
+        None => Envelope::null(),
+    };
+    for (key, value) in object {
+        if key == SUBJECT_KEY {
+            continue;
+        }
+        let predicate = key_to_predicate(key)?;
+        let values = match value {
+            Value::Array(elements) => elements.iter().collect(),
+            _ => vec![value],
+        };
+        for value in values {
+            envelope = envelope.add_assertion(predicate.clone(), value_to_envelope(value)?);
+        }
+    }
+    Ok(envelope)
+}
+
+fn key_to_predicate(key: &str) -> Result<Envelope> {
+    if let Some(name) = key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')) {
+        parse_data_type_to_envelope(DataType::Known, Some(name), None)
+    } else {
+        Ok(Envelope::new(key))
+    }
+}
+
+fn value_to_envelope(value: &Value) -> Result<Envelope> {
+    let envelope = match value {
+        Value::Null => Envelope::null(),
+        Value::Bool(b) => Envelope::new(*b),
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                Envelope::new(n)
+            } else if let Some(n) = n.as_i64() {
+                Envelope::new(n)
+            } else if let Some(n) = n.as_f64() {
+                Envelope::new(n)
+            } else {
+                bail!("Invalid number: {}", n)
+            }
+        }
+        Value::String(s) => Envelope::new(s.as_str()),
+        Value::Array(_) => bail!("Arrays are only supported as the value of an object key"),
+        Value::Object(object) => {
+            if is_typed_value(object) {
+                typed_value_to_envelope(object)?
+            } else {
+                object_to_envelope(object)?.wrap_envelope()
+            }
+        }
+    };
+    Ok(envelope)
+}
+
+fn is_typed_value(object: &Map<String, Value>) -> bool {
+    object.contains_key(TYPE_KEY)
+}
+
+fn typed_value_to_envelope(object: &Map<String, Value>) -> Result<Envelope> {
+    let data_type = match object.get(TYPE_KEY) {
+        Some(Value::String(s)) => DataType::from_str(s, true).map_err(|e| anyhow::anyhow!(e))?,
+        _ => bail!("`{}` must be a string", TYPE_KEY),
+    };
+    let value = match object.get(VALUE_KEY) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::Bool(b)) => b.to_string(),
+        _ => bail!("`{}` must be a string, number, or boolean", VALUE_KEY),
+    };
+    let tag = match object.get(TAG_KEY) {
+        Some(tag) => Some(tag.as_u64().ok_or_else(|| anyhow::anyhow!("`{}` must be an integer", TAG_KEY))?),
+        None => None,
+    };
+    parse_data_type_to_envelope(data_type, Some(&value), tag)
+}
+
+/// Convert an envelope back into a JSON document.
+///
+/// This is the inverse of `document_to_envelope`. Because an envelope's
+/// assertions are ordered by digest, keys are emitted in sorted order and
+/// repeated assertions are emitted in digest order. Elements that have no
+/// direct JSON equivalent are emitted as typed values.
+pub fn envelope_to_document(envelope: &Envelope) -> Result<Value> {
+    if envelope.is_node() {
+        node_to_document(envelope)
+    } else if envelope.is_wrapped() {
+        Ok(typed_value("wrapped", envelope.unwrap_envelope()?.ur_string()))
+    } else {
+        value_to_document(envelope)
+    }
+}
+
+fn node_to_document(envelope: &Envelope) -> Result<Value> {
+    let mut fields: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for assertion in envelope.assertions() {
+        let key = predicate_to_key(&assertion.try_predicate()?)?;
+        let value = value_to_document(&assertion.try_object()?)?;
+        fields.entry(key).or_default().push(value);
+    }
+    let mut object = Map::new();
+    let subject = envelope.subject();
+    if !subject.is_null() {
+        object.insert(SUBJECT_KEY.to_string(), value_to_document(&subject)?);
+    }
+    for (key, mut values) in fields {
+        let value = if values.len() == 1 { values.remove(0) } else { Value::Array(values) };
+        object.insert(key, value);
+    }
+    Ok(Value::Object(object))
+}
+
+fn predicate_to_key(predicate: &Envelope) -> Result<String> {
+    if let Some(known_value) = predicate.as_known_value() {
+        let name = with_format_context!(|context: &FormatContext| {
+            KnownValuesStore::name_for_known_value(known_value.clone(), Some(context.known_values()))
+        });
+        Ok(format!("'{}'", name))
+    } else if let Ok(key) = predicate.extract_subject::<String>() {
+        Ok(key)
+    } else {
+        bail!("Predicate cannot be represented as a document key: {}", predicate.format_flat())
+    }
+}
+
+fn value_to_document(envelope: &Envelope) -> Result<Value> {
+    if envelope.is_wrapped() {
+        let unwrapped = envelope.unwrap_envelope()?;
+        return if unwrapped.is_node() {
+            node_to_document(&unwrapped)
+        } else {
+            Ok(typed_value("wrapped", unwrapped.ur_string()))
+        };
+    }
+    if let Some(known_value) = envelope.as_known_value() {
+        let name = with_format_context!(|context: &FormatContext| {
+            KnownValuesStore::name_for_known_value(known_value.clone(), Some(context.known_values()))
+        });
+        return Ok(typed_value("known", name));
+    }
+    let cbor = match envelope.as_leaf() {
+        Some(cbor) => cbor,
+        None => return Ok(typed_value("envelope", envelope.ur_string())),
+    };
+    let value = match cbor.as_case() {
+        CBORCase::Unsigned(n) => json!(n),
+        CBORCase::Negative(_) => json!(i64::try_from(cbor.clone())?),
+        CBORCase::Text(s) => json!(s),
+        CBORCase::Simple(Simple::True) => json!(true),
+        CBORCase::Simple(Simple::False) => json!(false),
+        CBORCase::Simple(Simple::Null) => Value::Null,
+        CBORCase::Simple(Simple::Float(f)) => json!(f),
+        CBORCase::ByteString(data) => typed_value("data", hex::encode(data.data())),
+        CBORCase::Tagged(_, _) => {
+            if let Ok(date) = dcbor::Date::try_from(cbor.clone()) {
+                typed_value("date", date.to_string())
+            } else if let Ok(arid) = envelope.extract_subject::<ARID>() {
+                typed_value("arid", arid.ur_string())
+            } else if let Ok(digest) = envelope.extract_subject::<Digest>() {
+                typed_value("digest", digest.ur_string())
+            } else if let Ok(uri) = envelope.extract_subject::<URI>() {
+                typed_value("uri", uri.to_string())
+            } else if let Ok(uuid) = envelope.extract_subject::<UUID>() {
+                typed_value("uuid", uuid.to_string())
+            } else {
+                typed_value("cbor", cbor.hex())
+            }
+        }
+        CBORCase::Array(_) | CBORCase::Map(_) => typed_value("cbor", cbor.hex()),
+    };
+    Ok(value)
+}
+
+fn typed_value(data_type: &str, value: String) -> Value {
+    json!({ TYPE_KEY: data_type, VALUE_KEY: value })
+}
@@ -0,0 +1,161 @@
+use std::{io::Write, sync::OnceLock};
+
+use anyhow::{anyhow, bail, Result};
+use bc_envelope::prelude::*;
+use bc_ur::{bytewords, MultipartDecoder, MultipartEncoder};
+use clap::ValueEnum;
+
+/// The encoding used to read or write envelopes.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// Uniform Resource (ur:envelope)
+    #[default]
+    Ur,
+
+    /// Tagged CBOR in hex
+    Hex,
+
+    /// Raw binary tagged CBOR (stdin/stdout only)
+    Binary,
+
+    /// Tagged CBOR as Bytewords
+    Bytewords,
+}
+
+static INPUT_ENCODING: OnceLock<Encoding> = OnceLock::new();
+static OUTPUT_ENCODING: OnceLock<Encoding> = OnceLock::new();
+
+/// Set the encodings used by all subcommands. May only be called once.
+pub fn set_encodings(input: Encoding, output: Encoding) -> Result<()> {
+    INPUT_ENCODING.set(input).map_err(|_| anyhow!("Input encoding already set"))?;
+    OUTPUT_ENCODING.set(output).map_err(|_| anyhow!("Output encoding already set"))?;
+    Ok(())
+}
+
+pub fn input_encoding() -> Encoding {
+    INPUT_ENCODING.get().copied().unwrap_or_default()
+}
+
+pub fn output_encoding() -> Encoding {
+    OUTPUT_ENCODING.get().copied().unwrap_or_default()
+}
+
+/// Decode an envelope from its textual representation in the given encoding.
+///
+/// `Encoding::Binary` is not textual and must be read with `read_binary_envelope`.
+pub fn decode_envelope(s: &str, encoding: Encoding) -> Result<Envelope> {
+    let s = s.trim();
+    match encoding {
+        Encoding::Ur => Envelope::from_ur_string(s),
+        Encoding::Hex => Envelope::from_tagged_cbor_data(hex::decode(s)?),
+        Encoding::Bytewords => {
+            let data = [bytewords::Style::Standard, bytewords::Style::Uri, bytewords::Style::Minimal]
+                .into_iter()
+                .find_map(|style| bytewords::decode(s, style).ok());
+            match data {
+                Some(data) => Envelope::from_tagged_cbor_data(data),
+                None => bail!("Invalid Bytewords"),
+            }
+        }
+        Encoding::Binary => bail!("Binary envelopes can only be read from stdin"),
+    }
+}
+
+/// Read a raw binary tagged CBOR envelope from stdin.
+pub fn read_binary_envelope() -> Result<Envelope> {
+    let mut data = Vec::new();
+    std::io::Read::read_to_end(&mut std::io::stdin(), &mut data)?;
+    if data.is_empty() {
+        bail!("No envelope provided");
+    }
+    Envelope::from_tagged_cbor_data(data)
+}
+
+/// Encode an envelope in the given textual encoding.
+pub fn encode_envelope(envelope: &Envelope, encoding: Encoding) -> Result<String> {
+    let data = envelope.tagged_cbor_data();
+    Ok(match encoding {
+        Encoding::Ur => envelope.ur_string(),
+        Encoding::Hex => hex::encode(data),
+        Encoding::Bytewords => bytewords::encode(data, bytewords::Style::Standard),
+        Encoding::Binary => bail!("Binary envelopes can only be written to stdout"),
+    })
+}
+
+/// Returns `true` if the string is one part of a multipart UR.
+pub fn is_multipart_ur(s: &str) -> bool {
+    let regex = regex::Regex::new(r"^ur:[a-z0-9-]+/\d+-\d+/").unwrap();
+    regex.is_match(s.trim())
+}
+
+/// Split a UR into the parts of a fountain-coded multipart UR.
+pub fn encode_multipart_ur(ur: &UR, max_fragment_len: usize) -> Result<Vec<String>> {
+    let mut encoder = MultipartEncoder::new(ur, max_fragment_len)?;
+    (0..encoder.parts_count()).map(|_| encoder.next_part()).collect()
+}
+
+/// Reassemble a multipart UR from whitespace-separated parts.
+///
+/// If `read_stdin` is true and the parts are not sufficient to reassemble the
+/// UR, further parts are read from stdin, one per line.
+pub fn decode_multipart_ur(parts: &str, read_stdin: bool) -> Result<UR> {
+    let mut decoder = MultipartDecoder::new();
+    for part in parts.split_whitespace() {
+        decoder.receive(part)?;
+    }
+    if read_stdin {
+        let mut line = String::new();
+        while !decoder.is_complete() && std::io::stdin().read_line(&mut line)? > 0 {
+            if !line.trim().is_empty() {
+                decoder.receive(line.trim())?;
+            }
+            line.clear();
+        }
+    }
+    match decoder.message()? {
+        Some(ur) => Ok(ur),
+        None => bail!("Incomplete multipart UR"),
+    }
+}
+
+/// Write the envelope produced by a subcommand to stdout in the output
+/// encoding.
+pub fn write_envelope(envelope: &Envelope) -> Result<()> {
+    match output_encoding() {
+        Encoding::Binary => {
+            let mut stdout = std::io::stdout();
+            stdout.write_all(&envelope.tagged_cbor_data())?;
+            stdout.flush()?;
+        }
+        encoding => println!("{}", encode_envelope(envelope, encoding)?),
+    }
+    Ok(())
+}
+
+/// Write the envelopes produced by a subcommand to stdout in the output
+/// encoding, joined by `separator`. Binary output has no separator, so it
+/// requires a single envelope.
+pub fn write_envelopes(envelopes: &[Envelope], separator: &str) -> Result<()> {
+    let encoding = output_encoding();
+    if encoding == Encoding::Binary {
+        bail!("Binary output requires the command to produce a single envelope");
+    }
+    if !envelopes.is_empty() {
+        let encoded = envelopes.iter().map(|e| encode_envelope(e, encoding)).collect::<Result<Vec<_>>>()?;
+        println!("{}", encoded.join(separator));
+    }
+    Ok(())
+}
+
+/// Write the text produced by a subcommand to stdout. The output encoding only
+/// applies to envelopes, so the text is written as is, unless binary output
+/// was requested, which text cannot satisfy.
+pub fn write_text(text: &str) -> Result<()> {
+    if output_encoding() == Encoding::Binary {
+        bail!("Binary output requires the command to produce a single envelope");
+    }
+    if !text.is_empty() {
+        println!("{}", text);
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*};
+
+use crate::envelope_json::case_name;
+
+/// The maximum length of the summary of a leaf in a node label.
+const SUMMARY_LENGTH: usize = 40;
+
+/// A node of the envelope graph, identified by its position in the walk.
+struct GraphNode {
+    id: usize,
+    envelope: Envelope,
+    summary: Option<String>,
+}
+
+/// An edge of the envelope graph, with the label used by `format --type tree`.
+struct GraphEdge {
+    from: usize,
+    to: usize,
+    label: Option<&'static str>,
+}
+
+/// The nodes and edges of an envelope, in the same order as its tree format.
+struct Graph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+impl Graph {
+    fn new(envelope: &Envelope) -> Self {
+        let mut graph = Self { nodes: Vec::new(), edges: Vec::new() };
+        with_format_context!(|context: &FormatContext| {
+            graph.add(envelope, None, context);
+        });
+        graph
+    }
+
+    fn add(&mut self, envelope: &Envelope, parent: Option<(usize, Option<&'static str>)>, context: &FormatContext) {
+        let id = self.nodes.len();
+        let summary = match envelope.case() {
+            EnvelopeCase::Leaf { .. } | EnvelopeCase::KnownValue { .. } => Some(envelope.summary(SUMMARY_LENGTH, context)),
+            _ => None,
+        };
+        self.nodes.push(GraphNode { id, envelope: envelope.clone(), summary });
+        if let Some((from, label)) = parent {
+            self.edges.push(GraphEdge { from, to: id, label });
+        }
+        match envelope.case() {
+            EnvelopeCase::Node { subject, assertions, .. } => {
+                self.add(subject, Some((id, Some("subj"))), context);
+                for assertion in assertions {
+                    self.add(assertion, Some((id, None)), context);
+                }
+            }
+            EnvelopeCase::Wrapped { envelope, .. } => self.add(envelope, Some((id, Some("subj"))), context),
+            EnvelopeCase::Assertion(assertion) => {
+                self.add(&assertion.predicate(), Some((id, Some("pred"))), context);
+                self.add(&assertion.object(), Some((id, Some("obj"))), context);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl GraphNode {
+    /// The lines of the node's label: its case and short digest, then its
+    /// summary if it is a leaf or known value.
+    fn label_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} {}", case_name(&self.envelope), self.envelope.short_id())];
+        lines.extend(self.summary.clone());
+        lines
+    }
+
+    /// The style class of an obscured node.
+    fn class(&self) -> Option<&'static str> {
+        match self.envelope.case() {
+            EnvelopeCase::Elided(_) => Some("elided"),
+            EnvelopeCase::Encrypted(_) => Some("encrypted"),
+            EnvelopeCase::Compressed(_) => Some("compressed"),
+            _ => None,
+        }
+    }
+}
+
+/// Render the envelope as a Graphviz DOT digraph.
+pub fn envelope_to_dot(envelope: &Envelope) -> String {
+    let graph = Graph::new(envelope);
+    let mut lines = vec![
+        "digraph envelope {".to_string(),
+        "    rankdir=LR;".to_string(),
+        "    node [shape=box, fontname=\"Menlo\"];".to_string(),
+    ];
+    for node in &graph.nodes {
+        let label = node.label_lines().iter().map(|line| dot_escape(line)).collect::<Vec<_>>().join("\\n");
+        let style = match node.class() {
+            Some("elided") => ", style=dashed, color=gray50, fontcolor=gray50",
+            Some("encrypted") => ", style=filled, fillcolor=lightpink",
+            Some("compressed") => ", style=filled, fillcolor=lightblue",
+            _ => "",
+        };
+        lines.push(format!("    n{} [label=\"{}\"{}];", node.id, label, style));
+    }
+    for edge in &graph.edges {
+        match edge.label {
+            Some(label) => lines.push(format!("    n{} -> n{} [label=\"{}\"];", edge.from, edge.to, label)),
+            None => lines.push(format!("    n{} -> n{};", edge.from, edge.to)),
+        }
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+/// Render the envelope as a Mermaid flowchart.
+pub fn envelope_to_mermaid(envelope: &Envelope) -> String {
+    let graph = Graph::new(envelope);
+    let mut lines = vec!["graph LR".to_string()];
+    for node in &graph.nodes {
+        let label = node.label_lines().iter().map(|line| mermaid_escape(line)).collect::<Vec<_>>().join("<br>");
+        lines.push(format!("    n{}[\"{}\"]", node.id, label));
+    }
+    for edge in &graph.edges {
+        match edge.label {
+            Some(label) => lines.push(format!("    n{} -->|{}| n{}", edge.from, label, edge.to)),
+            None => lines.push(format!("    n{} --> n{}", edge.from, edge.to)),
+        }
+    }
+    let classes = [
+        ("elided", "stroke-dasharray:5 5,stroke:#808080,color:#808080"),
+        ("encrypted", "fill:#ffb6c1"),
+        ("compressed", "fill:#add8e6"),
+    ];
+    for (class, style) in classes {
+        let ids = graph.nodes.iter()
+            .filter(|node| node.class() == Some(class))
+            .map(|node| format!("n{}", node.id))
+            .collect::<Vec<_>>();
+        if !ids.is_empty() {
+            lines.push(format!("    classDef {} {}", class, style));
+            lines.push(format!("    class {} {}", ids.join(","), class));
+        }
+    }
+    lines.join("\n")
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;")
+}
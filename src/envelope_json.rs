@@ -0,0 +1,102 @@
+use bc_envelope::{base::envelope::EnvelopeCase, prelude::*};
+use dcbor::Simple;
+use serde_json::{json, Map, Number, Value};
+
+/// Convert an envelope into a structured JSON tree.
+///
+/// Every node becomes a JSON object carrying its `case`, its `digest`
+/// (ur:digest), and the fields specific to that case.
+pub fn envelope_to_json(envelope: &Envelope) -> Value {
+    with_format_context!(|context: &FormatContext| {
+        envelope_to_json_opt(envelope, context)
+    })
+}
+
+fn envelope_to_json_opt(envelope: &Envelope, context: &FormatContext) -> Value {
+    let mut object = Map::new();
+    object.insert("case".to_string(), json!(case_name(envelope)));
+    object.insert("digest".to_string(), json!(envelope.digest().ur_string()));
+    match envelope.case() {
+        EnvelopeCase::Node { subject, assertions, .. } => {
+            object.insert("subject".to_string(), envelope_to_json_opt(subject, context));
+            let assertions = assertions.iter().map(|a| envelope_to_json_opt(a, context)).collect();
+            object.insert("assertions".to_string(), Value::Array(assertions));
+        }
+        EnvelopeCase::Leaf { cbor, .. } => {
+            object.insert("cbor".to_string(), json!(cbor.hex()));
+            object.insert("value".to_string(), cbor_to_json(cbor));
+            object.insert("summary".to_string(), json!(envelope.summary(usize::MAX, context)));
+        }
+        EnvelopeCase::Wrapped { envelope, .. } => {
+            object.insert("envelope".to_string(), envelope_to_json_opt(envelope, context));
+        }
+        EnvelopeCase::Assertion(assertion) => {
+            object.insert("predicate".to_string(), envelope_to_json_opt(&assertion.predicate(), context));
+            object.insert("object".to_string(), envelope_to_json_opt(&assertion.object(), context));
+        }
+        EnvelopeCase::KnownValue { value, .. } => {
+            let known_value = KnownValuesStore::known_value_for_raw_value(value.value(), Some(context.known_values()));
+            object.insert("value".to_string(), json!(known_value.value()));
+            object.insert("name".to_string(), json!(known_value.name()));
+        }
+        EnvelopeCase::Elided(_) | EnvelopeCase::Encrypted(_) | EnvelopeCase::Compressed(_) => {}
+    }
+    Value::Object(object)
+}
+
+/// The name used for the `case` field of a node.
+pub fn case_name(envelope: &Envelope) -> &'static str {
+    match envelope.case() {
+        EnvelopeCase::Node { .. } => "node",
+        EnvelopeCase::Leaf { .. } => "leaf",
+        EnvelopeCase::Wrapped { .. } => "wrapped",
+        EnvelopeCase::Assertion(_) => "assertion",
+        EnvelopeCase::Elided(_) => "elided",
+        EnvelopeCase::KnownValue { .. } => "knownValue",
+        EnvelopeCase::Encrypted(_) => "encrypted",
+        EnvelopeCase::Compressed(_) => "compressed",
+    }
+}
+
+/// Decode a CBOR value into the closest JSON equivalent.
+///
+/// Byte strings become hex strings, dates become ISO 8601 strings, and other
+/// tagged values become an object with `tag` and `value` fields.
+pub fn cbor_to_json(cbor: &CBOR) -> Value {
+    match cbor.as_case() {
+        CBORCase::Unsigned(n) => json!(n),
+        CBORCase::Negative(n) => {
+            let n = -1 - (*n as i128);
+            match i64::try_from(n) {
+                Ok(n) => json!(n),
+                Err(_) => json!(n.to_string()),
+            }
+        }
+        CBORCase::ByteString(data) => json!(hex::encode(data.data())),
+        CBORCase::Text(string) => json!(string),
+        CBORCase::Array(elements) => Value::Array(elements.iter().map(cbor_to_json).collect()),
+        CBORCase::Map(map) => {
+            let object = map.iter().map(|(key, value)| {
+                let key = match key.as_case() {
+                    CBORCase::Text(string) => string.clone(),
+                    _ => key.diagnostic_flat(),
+                };
+                (key, cbor_to_json(value))
+            }).collect();
+            Value::Object(object)
+        }
+        CBORCase::Tagged(tag, item) => {
+            if let Ok(date) = dcbor::Date::try_from(cbor.clone()) {
+                json!(date.to_string())
+            } else {
+                json!({ "tag": tag.value(), "value": cbor_to_json(item) })
+            }
+        }
+        CBORCase::Simple(simple) => match simple {
+            Simple::True => json!(true),
+            Simple::False => json!(false),
+            Simple::Null => Value::Null,
+            Simple::Float(f) => Number::from_f64(*f).map(Value::Number).unwrap_or(Value::Null),
+        },
+    }
+}
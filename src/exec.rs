@@ -1,5 +1,52 @@
 use anyhow::Result;
+use bc_envelope::prelude::*;
+
+/// The result of executing a command.
+#[derive(Debug, Clone)]
+pub enum Output {
+    /// The command produced an envelope.
+    Envelope(Envelope),
+    /// The command produced several envelopes, written joined by the
+    /// separator.
+    Envelopes(Vec<Envelope>, &'static str),
+    /// The command produced text.
+    Text(String),
+}
+
+impl std::fmt::Display for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Output::Envelope(envelope) => write!(f, "{}", envelope.ur_string()),
+            Output::Envelopes(envelopes, separator) => {
+                write!(f, "{}", envelopes.iter().map(|e| e.ur_string()).collect::<Vec<_>>().join(separator))
+            }
+            Output::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
 
 pub trait Exec {
     fn exec(&self) -> Result<String>;
+
+    /// Execute the command, keeping its result as an envelope if it produces
+    /// one, so a pipeline can pass it to the next command without a UR round
+    /// trip.
+    fn exec_output(&self) -> Result<Output> {
+        self.exec().map(Output::Text)
+    }
+}
+
+/// A command whose result is a single envelope.
+pub trait ExecEnvelope {
+    fn exec_envelope(&self) -> Result<Envelope>;
+}
+
+impl<T: ExecEnvelope> Exec for T {
+    fn exec(&self) -> Result<String> {
+        Ok(self.exec_envelope()?.ur_string())
+    }
+
+    fn exec_output(&self) -> Result<Output> {
+        self.exec_envelope().map(Output::Envelope)
+    }
 }
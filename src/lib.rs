@@ -1 +1,16 @@
+//! A library and command line tool for manipulating the Gordian Envelope data
+//! type. See the main repo [README](https://github.com/BlockchainCommons/bc-envelope-cli-rust/blob/master/README.md).
+//!
+//! The [`api`] module exposes the operations of the `envelope` commands as
+//! typed functions.
+
+pub mod api;
+
+#[doc(hidden)]
+pub mod encoding;
+#[doc(hidden)]
+pub mod pipeline;
+#[doc(hidden)]
+pub mod qr_image;
+#[doc(hidden)]
 pub mod utils;
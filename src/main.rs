@@ -9,14 +9,19 @@ mod styles;
 #[doc(hidden)]
 mod data_types;
 #[doc(hidden)]
+mod document;
+#[doc(hidden)]
 mod pred_obj_args;
 #[doc(hidden)]
 mod envelope_args;
 #[doc(hidden)]
-mod subject_args;
+mod envelope_graph;
+#[doc(hidden)]
+mod envelope_json;
 #[doc(hidden)]
-mod utils;
+mod subject_args;
 
+use bc_envelope_cli::{api, encoding, pipeline, qr_image, utils};
 use clap::{Parser, Subcommand};
 use anyhow::Result;
 
@@ -29,6 +34,14 @@ use crate::exec::Exec;
 #[command(styles=styles::get_styles())]
 #[doc(hidden)]
 struct Cli {
+    /// The encoding of the input envelope.
+    #[arg(long = "in", global = true, default_value = "ur")]
+    input: encoding::Encoding,
+
+    /// The encoding of the output envelopes, for commands that produce them.
+    #[arg(long = "out", global = true, default_value = "ur")]
+    output: encoding::Encoding,
+
     #[command(subcommand)]
     command: MainCommands,
 }
@@ -40,6 +53,7 @@ enum MainCommands {
     Attachment(cmd::attachment::CommandArgs),
     Compress(cmd::compress::CommandArgs),
     Decrypt(cmd::decrypt::CommandArgs),
+    Diff(cmd::diff::CommandArgs),
     Digest(cmd::digest::CommandArgs),
     Elide(cmd::elide::CommandArgs),
     Encrypt(cmd::encrypt::CommandArgs),
@@ -49,8 +63,13 @@ enum MainCommands {
     Generate(cmd::generate::CommandArgs),
     Import(cmd::import::CommandArgs),
     Info(cmd::info::CommandArgs),
+    Patch(cmd::patch::CommandArgs),
     Proof(cmd::proof::CommandArgs),
+    Qr(cmd::qr::CommandArgs),
+    Query(cmd::query::CommandArgs),
+    Run(cmd::run::CommandArgs),
     Salt(cmd::salt::CommandArgs),
+    Shell(cmd::shell::CommandArgs),
     Sign(cmd::sign::CommandArgs),
     Sskr(cmd::sskr::CommandArgs),
     Subject(cmd::subject::CommandArgs),
@@ -59,38 +78,82 @@ enum MainCommands {
     Xid(cmd::xid::CommandArgs),
 }
 
+impl Exec for MainCommands {
+    fn exec(&self) -> Result<String> {
+        match self {
+            MainCommands::Assertion(args) => args.exec(),
+            MainCommands::Attachment(args) => args.exec(),
+            MainCommands::Compress(args) => args.exec(),
+            MainCommands::Decrypt(args) => args.exec(),
+            MainCommands::Diff(args) => args.exec(),
+            MainCommands::Digest(args) => args.exec(),
+            MainCommands::Elide(args) => args.exec(),
+            MainCommands::Encrypt(args) => args.exec(),
+            MainCommands::Export(args) => args.exec(),
+            MainCommands::Extract(args) => args.exec(),
+            MainCommands::Format(args) => args.exec(),
+            MainCommands::Generate(args) => args.exec(),
+            MainCommands::Import(args) => args.exec(),
+            MainCommands::Info(args) => args.exec(),
+            MainCommands::Patch(args) => args.exec(),
+            MainCommands::Proof(args) => args.exec(),
+            MainCommands::Qr(args) => args.exec(),
+            MainCommands::Query(args) => args.exec(),
+            MainCommands::Run(args) => args.exec(),
+            MainCommands::Salt(args) => args.exec(),
+            MainCommands::Shell(args) => args.exec(),
+            MainCommands::Sign(args) => args.exec(),
+            MainCommands::Sskr(args) => args.exec(),
+            MainCommands::Subject(args) => args.exec(),
+            MainCommands::Uncompress(args) => args.exec(),
+            MainCommands::Verify(args) => args.exec(),
+            MainCommands::Xid(args) => args.exec(),
+        }
+    }
+
+    fn exec_output(&self) -> Result<exec::Output> {
+        match self {
+            MainCommands::Assertion(args) => args.exec_output(),
+            MainCommands::Attachment(args) => args.exec_output(),
+            MainCommands::Compress(args) => args.exec_output(),
+            MainCommands::Decrypt(args) => args.exec_output(),
+            MainCommands::Diff(args) => args.exec_output(),
+            MainCommands::Digest(args) => args.exec_output(),
+            MainCommands::Elide(args) => args.exec_output(),
+            MainCommands::Encrypt(args) => args.exec_output(),
+            MainCommands::Export(args) => args.exec_output(),
+            MainCommands::Extract(args) => args.exec_output(),
+            MainCommands::Format(args) => args.exec_output(),
+            MainCommands::Generate(args) => args.exec_output(),
+            MainCommands::Import(args) => args.exec_output(),
+            MainCommands::Info(args) => args.exec_output(),
+            MainCommands::Patch(args) => args.exec_output(),
+            MainCommands::Proof(args) => args.exec_output(),
+            MainCommands::Qr(args) => args.exec_output(),
+            MainCommands::Query(args) => args.exec_output(),
+            MainCommands::Run(args) => args.exec_output(),
+            MainCommands::Salt(args) => args.exec_output(),
+            MainCommands::Shell(args) => args.exec_output(),
+            MainCommands::Sign(args) => args.exec_output(),
+            MainCommands::Sskr(args) => args.exec_output(),
+            MainCommands::Subject(args) => args.exec_output(),
+            MainCommands::Uncompress(args) => args.exec_output(),
+            MainCommands::Verify(args) => args.exec_output(),
+            MainCommands::Xid(args) => args.exec_output(),
+        }
+    }
+}
+
 #[doc(hidden)]
 fn main() -> Result<()> {
     bc_envelope::register_tags();
 
     let cli = Cli::parse();
+    encoding::set_encodings(cli.input, cli.output)?;
 
-    let output = match cli.command {
-        MainCommands::Assertion(args) => args.exec(),
-        MainCommands::Attachment(args) => args.exec(),
-        MainCommands::Compress(args) => args.exec(),
-        MainCommands::Decrypt(args) => args.exec(),
-        MainCommands::Digest(args) => args.exec(),
-        MainCommands::Elide(args) => args.exec(),
-        MainCommands::Encrypt(args) => args.exec(),
-        MainCommands::Export(args) => args.exec(),
-        MainCommands::Extract(args) => args.exec(),
-        MainCommands::Format(args) => args.exec(),
-        MainCommands::Generate(args) => args.exec(),
-        MainCommands::Import(args) => args.exec(),
-        MainCommands::Info(args) => args.exec(),
-        MainCommands::Proof(args) => args.exec(),
-        MainCommands::Salt(args) => args.exec(),
-        MainCommands::Sign(args) => args.exec(),
-        MainCommands::Sskr(args) => args.exec(),
-        MainCommands::Subject(args) => args.exec(),
-        MainCommands::Uncompress(args) => args.exec(),
-        MainCommands::Verify(args) => args.exec(),
-        MainCommands::Xid(args) => args.exec(),
-    };
-    let output = output?;
-    if !output.is_empty() {
-        println!("{}", output);
+    match cli.command.exec_output()? {
+        exec::Output::Envelope(envelope) => encoding::write_envelope(&envelope),
+        exec::Output::Envelopes(envelopes, separator) => encoding::write_envelopes(&envelopes, separator),
+        exec::Output::Text(text) => encoding::write_text(&text),
     }
-    Ok(())
 }
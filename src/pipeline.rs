@@ -0,0 +1,38 @@
+use std::cell::RefCell;
+
+use bc_envelope::prelude::*;
+
+thread_local! {
+    /// `Some` while commands are being run in a pipeline, holding its current
+    /// envelope, if any.
+    static CURRENT_ENVELOPE: RefCell<Option<Option<Envelope>>> = const { RefCell::new(None) };
+}
+
+/// Start running commands in a pipeline. Until `end` is called, commands that
+/// would read their envelope from stdin use the current envelope instead, and
+/// other arguments must be given explicitly.
+pub fn begin() {
+    CURRENT_ENVELOPE.with(|current| *current.borrow_mut() = Some(None));
+}
+
+/// Stop running commands in a pipeline.
+pub fn end() {
+    CURRENT_ENVELOPE.with(|current| *current.borrow_mut() = None);
+}
+
+/// Returns `true` if commands are being run in a pipeline.
+pub fn is_running() -> bool {
+    CURRENT_ENVELOPE.with(|current| current.borrow().is_some())
+}
+
+pub fn current_envelope() -> Option<Envelope> {
+    CURRENT_ENVELOPE.with(|current| current.borrow().clone().flatten())
+}
+
+pub fn set_current_envelope(envelope: Option<Envelope>) {
+    CURRENT_ENVELOPE.with(|current| {
+        if let Some(current) = current.borrow_mut().as_mut() {
+            *current = envelope;
+        }
+    });
+}
@@ -0,0 +1,106 @@
+use std::{fs::File, io::BufReader, path::{Path, PathBuf}};
+
+use anyhow::{bail, Result};
+use bc_ur::{MultipartDecoder, UR};
+use image::{codecs::gif::GifDecoder, AnimationDecoder, DynamicImage, GrayImage};
+
+use crate::encoding::is_multipart_ur;
+
+/// The image file extensions that are searched for QR codes.
+const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "gif"];
+
+/// Returns `true` if the argument names an image file or a directory
+/// containing image files that may contain QR codes.
+pub fn is_qr_image_path(argument: &str) -> bool {
+    if argument.starts_with("ur:") {
+        return false;
+    }
+    let path = Path::new(argument);
+    if path.is_dir() {
+        image_paths(path).is_ok_and(|paths| !paths.is_empty())
+    } else {
+        path.is_file() && is_image_file(path)
+    }
+}
+
+/// Read a UR from the QR codes in an image.
+///
+/// `path` may be a PNG, JPEG, or GIF file, or a directory of such files. The
+/// frames of an animated GIF and the images in a directory are treated as the
+/// parts of a multipart UR, and are reassembled into a single UR.
+pub fn read_ur_from_qr_image(path: &Path) -> Result<String> {
+    let images = if path.is_dir() {
+        let mut images = Vec::new();
+        for path in image_paths(path)? {
+            images.extend(load_images(&path)?);
+        }
+        images
+    } else {
+        load_images(path)?
+    };
+
+    // A QR code that fails to decode, such as a blurry frame, is skipped, as
+    // the other parts may still be enough to reassemble a multipart UR.
+    let mut contents = Vec::new();
+    let mut undecodable = 0;
+    for image in images {
+        let mut image = rqrr::PreparedImage::prepare(image);
+        for grid in image.detect_grids() {
+            let Ok((_, content)) = grid.decode() else {
+                undecodable += 1;
+                continue;
+            };
+            let content = content.trim().to_lowercase();
+            if !contents.contains(&content) {
+                contents.push(content);
+            }
+        }
+    }
+
+    match contents.as_slice() {
+        [] if undecodable > 0 => bail!("None of the QR codes in {} could be decoded", path.display()),
+        [] => bail!("No QR code found in {}", path.display()),
+        [content] if !is_multipart_ur(content) => Ok(UR::from_ur_string(content)?.string()),
+        _ => {
+            let mut decoder = MultipartDecoder::new();
+            for content in &contents {
+                if !is_multipart_ur(content) {
+                    bail!("Found several QR codes that are not parts of a multipart UR in {}", path.display());
+                }
+                decoder.receive(content)?;
+            }
+            match decoder.message()? {
+                Some(ur) => Ok(ur.string()),
+                None => bail!("Incomplete multipart UR in {}", path.display()),
+            }
+        }
+    }
+}
+
+/// The image files in a directory, sorted by name.
+fn image_paths(directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = std::fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    paths.retain(|path| path.is_file() && is_image_file(path));
+    paths.sort();
+    Ok(paths)
+}
+
+fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .is_some_and(|extension| IMAGE_EXTENSIONS.contains(&extension.as_str()))
+}
+
+/// Load the grayscale frames of an image, one per frame for an animated GIF.
+fn load_images(path: &Path) -> Result<Vec<GrayImage>> {
+    let is_gif = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+    if is_gif {
+        let decoder = GifDecoder::new(BufReader::new(File::open(path)?))?;
+        let frames = decoder.into_frames().collect_frames()?;
+        Ok(frames.into_iter().map(|frame| DynamicImage::ImageRgba8(frame.into_buffer()).into_luma8()).collect())
+    } else {
+        Ok(vec![image::open(path)?.into_luma8()])
+    }
+}
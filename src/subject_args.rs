@@ -1,6 +1,6 @@
 use anyhow::{bail, Result};
 use clap::Args;
-use crate::data_types::DataType;
+use crate::{data_types::DataType, pipeline};
 
 pub trait SubjectArgsLike {
     fn subject_type(&self) -> DataType;
@@ -10,7 +10,9 @@ pub trait SubjectArgsLike {
     fn read_subject_value(&self) -> Result<String> {
         let mut string = String::new();
         if self.subject_value().is_none() {
-            std::io::stdin().read_line(&mut string)?;
+            if !pipeline::is_running() {
+                std::io::stdin().read_line(&mut string)?;
+            }
         } else {
             string = self.subject_value().as_ref().unwrap().to_string();
         }
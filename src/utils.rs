@@ -1,9 +1,11 @@
-use std::{collections::HashSet, io::Read};
-use anyhow::{ bail, Result };
+use std::{collections::HashSet, io::Read, path::Path};
+use anyhow::{ anyhow, bail, Result };
 use bc_components::XID;
 use bc_envelope::prelude::*;
 use bc_xid::XIDDocument;
 
+use crate::{encoding::{decode_envelope, decode_multipart_ur, input_encoding, is_multipart_ur, read_binary_envelope, Encoding}, pipeline, qr_image::{is_qr_image_path, read_ur_from_qr_image}};
+
 pub fn read_password(prompt: &str, password: Option<&str>) -> Result<String> {
     if let Some(password) = password {
         Ok(password.to_string())
@@ -12,10 +14,13 @@ pub fn read_password(prompt: &str, password: Option<&str>) -> Result<String> {
     }
 }
 
+#[allow(clippy::unnecessary_unwrap)]
 pub fn read_argument(argument: Option<&str>) -> Result<String> {
     let mut string = String::new();
     if argument.is_none() {
-        std::io::stdin().read_to_string(&mut string)?;
+        if !pipeline::is_running() {
+            std::io::stdin().read_to_string(&mut string)?;
+        }
     } else {
         string = argument.as_ref().unwrap().to_string();
     }
@@ -25,7 +30,18 @@ pub fn read_argument(argument: Option<&str>) -> Result<String> {
     Ok(string.to_string())
 }
 
+#[allow(clippy::unnecessary_unwrap)]
 pub fn read_envelope(envelope: Option<&str>) -> Result<Envelope> {
+    if envelope.is_none() && pipeline::is_running() {
+        return pipeline::current_envelope().ok_or_else(|| anyhow!("No current envelope"));
+    }
+    let encoding = input_encoding();
+    if encoding == Encoding::Binary {
+        if envelope.is_some() {
+            bail!("Binary envelopes can only be read from stdin");
+        }
+        return read_binary_envelope();
+    }
     let mut ur_string = String::new();
     if envelope.is_none() {
         std::io::stdin().read_line(&mut ur_string)?;
@@ -35,11 +51,25 @@ pub fn read_envelope(envelope: Option<&str>) -> Result<Envelope> {
     if ur_string.is_empty() {
         bail!("No envelope provided");
     }
+    if is_qr_image_path(ur_string.trim()) {
+        ur_string = read_ur_from_qr_image(Path::new(ur_string.trim()))?;
+    } else if encoding != Encoding::Ur {
+        return decode_envelope(&ur_string, encoding);
+    }
+    if is_multipart_ur(&ur_string) {
+        ur_string = decode_multipart_ur(&ur_string, envelope.is_none())?.string();
+    }
+    parse_envelope_ur(ur_string.trim())
+}
+
+/// Parse an envelope from a UR, ignoring `--in`. Used for envelopes given as
+/// option arguments, such as signers and detached signatures.
+pub fn parse_envelope_ur(ur_string: &str) -> Result<Envelope> {
     // Just try to parse the envelope as a ur:envelope string first
-    if let Ok(envelope) = Envelope::from_ur_string(ur_string.trim()) {
+    if let Ok(envelope) = Envelope::from_ur_string(ur_string) {
         Ok(envelope)
     // If that fails, try to parse the envelope as a ur:<any> string
-    } else if let Ok(ur) = UR::from_ur_string(ur_string.trim()) {
+    } else if let Ok(ur) = UR::from_ur_string(ur_string) {
         let cbor = ur.cbor();
         // Try to parse the CBOR into an envelope
         if let Ok(envelope) = Envelope::from_tagged_cbor(cbor) {
@@ -49,7 +79,7 @@ pub fn read_envelope(envelope: Option<&str>) -> Result<Envelope> {
             let doc = XIDDocument::from(xid);
             Ok(doc.into_envelope())
         } else {
-            todo!();
+            bail!("Invalid envelope");
         }
     } else {
         bail!("Invalid envelope");
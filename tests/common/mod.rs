@@ -117,3 +117,45 @@ pub fn run_cli_piped(cmds: &[&[&str]]) -> Result<String> {
 pub fn run_cli_raw_piped(cmds: &[&[&str]]) -> Result<String> {
     run_cli_raw_piped_stdin(cmds, "")
 }
+
+/// A file or directory path in the system temporary directory, removed along
+/// with its contents when dropped.
+pub struct TempPath(std::path::PathBuf);
+
+impl TempPath {
+    /// A path for a file named after `name`, which is not created.
+    pub fn file(name: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("envelope-test-{}-{}", name, std::process::id())))
+    }
+
+    /// A newly created directory named after `name`.
+    pub fn dir(name: &str) -> Self {
+        let path = Self::file(name);
+        std::fs::create_dir_all(&path.0).unwrap();
+        path
+    }
+}
+
+impl std::ops::Deref for TempPath {
+    type Target = std::path::Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<std::path::Path> for TempPath {
+    fn as_ref(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if self.0.is_dir() {
+            let _ = std::fs::remove_dir_all(&self.0);
+        } else {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use bc_components::{EncapsulationScheme, PrivateKeyBase, PublicKeys, PublicKeysProvider, SSKRGroupSpec, SSKRSpec, Signer, SigningOptions, SymmetricKey, Verifier};
+use bc_envelope::prelude::*;
+use bc_envelope_cli::api;
+use bc_xid::{HasName, Privilege};
+
+mod common;
+use common::*;
+
+fn alice_knows_bob() -> Envelope {
+    bc_envelope::register_tags();
+    Envelope::from_ur_string(ALICE_KNOWS_BOB_EXAMPLE).unwrap()
+}
+
+#[test]
+fn test_api_sign_verify() -> Result<()> {
+    let envelope = alice_knows_bob();
+    let alice = PrivateKeyBase::from_ur_string(ALICE_PRVKEY_BASE)?;
+    let bob = PrivateKeyBase::from_ur_string(BOB_PRVKEY_BASE)?;
+
+    let signers: Vec<(&dyn Signer, Option<SigningOptions>)> = vec![(&alice, None)];
+    let signed = api::sign::sign(&envelope, &signers, Some("Signed by Alice"))?;
+    assert_eq!(signed.subject().digest(), envelope.subject().digest());
+
+    let alice_public = PublicKeys::from_ur_string(ALICE_PUBKEYS)?;
+    api::sign::verify(&signed, &[&alice_public as &dyn Verifier], 1)?;
+    assert!(api::sign::verify(&signed, &[&bob as &dyn Verifier], 1).is_err());
+    assert!(api::sign::verify(&signed, &[], 1).is_err());
+
+    // A note may only be added to a single signature.
+    let signers: Vec<(&dyn Signer, Option<SigningOptions>)> = vec![(&alice, None), (&bob, None)];
+    assert!(api::sign::sign(&envelope, &signers, Some("Signed by Alice")).is_err());
+    api::sign::sign(&envelope, &signers, None)?;
+
+    // The library and the command line tool agree.
+    let output = run_cli(&["verify", "--verifier", ALICE_PUBKEYS, &signed.ur_string()])?;
+    assert_eq!(output, signed.ur_string());
+    Ok(())
+}
+
+#[test]
+fn test_api_encrypt_decrypt() -> Result<()> {
+    let envelope = alice_knows_bob();
+    let bob = PrivateKeyBase::from_ur_string(BOB_PRVKEY_BASE)?;
+    let bob_public = PublicKeys::from_ur_string(BOB_PUBKEYS)?;
+
+    assert!(api::encrypt::encrypt(&envelope, None, &[]).is_err());
+    let encrypted = api::encrypt::encrypt(&envelope, None, &[bob_public])?;
+    assert!(encrypted.subject().is_encrypted());
+    let decrypted = api::encrypt::decrypt_to_recipient(&encrypted, &bob)?;
+    assert_eq!(decrypted.subject().digest(), envelope.subject().digest());
+
+    let alice = PrivateKeyBase::from_ur_string(ALICE_PRVKEY_BASE)?;
+    let (decrypted, index) = api::encrypt::decrypt_to_any_recipient(&encrypted, &[&alice, &bob])?;
+    assert_eq!(index, 1);
+    assert_eq!(decrypted.subject().digest(), envelope.subject().digest());
+    assert!(api::encrypt::decrypt_to_any_recipient(&encrypted, &[&alice]).is_err());
+
+    let content_key = api::encrypt::content_key(&encrypted, &bob)?;
+    let shared = api::encrypt::add_recipient(&encrypted, &content_key, &alice.public_keys())?;
+    let unshared = api::encrypt::remove_recipient(&shared, &bob)?;
+    assert!(api::encrypt::decrypt_to_recipient(&unshared, &bob).is_err());
+    assert_eq!(api::encrypt::decrypt_to_recipient(&unshared, &alice)?.subject().digest(), envelope.subject().digest());
+    Ok(())
+}
+
+#[test]
+fn test_api_password_limits() -> Result<()> {
+    let envelope = alice_knows_bob();
+    let encrypted = api::encrypt::encrypt_with_password(&envelope, "Correct-Horse")?;
+    api::encrypt::password_key(&encrypted, "Correct-Horse")?;
+
+    // Parameters read from an untrusted envelope are bounded.
+    let with_parameter = |predicate: &str, value: u32| -> Result<Envelope> {
+        let kdf_assertion = encrypted.assertion_with_predicate(api::encrypt::KDF)?;
+        let kdf = kdf_assertion.try_object()?;
+        let kdf = kdf.remove_assertion(kdf.assertion_with_predicate(predicate)?)
+            .add_assertion(predicate, value);
+        Ok(encrypted.remove_assertion(kdf_assertion).add_assertion(api::encrypt::KDF, kdf))
+    };
+    for (predicate, value) in [
+        ("memory", api::encrypt::MAX_MEMORY + 1),
+        ("memory", u32::MAX),
+        ("iterations", u32::MAX),
+        ("parallelism", api::encrypt::MAX_PARALLELISM + 1),
+    ] {
+        let crafted = with_parameter(predicate, value)?;
+        assert!(api::encrypt::password_key(&crafted, "Correct-Horse").is_err());
+        assert!(run_cli(&["decrypt", "--password=Correct-Horse", &crafted.ur_string()]).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_api_encryption_info() -> Result<()> {
+    let envelope = alice_knows_bob();
+    assert!(api::encrypt::encryption_info(&envelope)?.is_none());
+
+    let key = SymmetricKey::new();
+    let (_, kyber_public) = EncapsulationScheme::Kyber768.keypair();
+    let encrypted = api::encrypt::encrypt(&envelope, Some(&key), &[PublicKeys::from_ur_string(BOB_PUBKEYS)?])?
+        .add_recipient(&kyber_public, &key);
+    let info = api::encrypt::encryption_info(&encrypted)?.unwrap();
+    assert_eq!(info.recipients.len(), 2);
+    assert!(info.recipients.contains(&Some(EncapsulationScheme::Kyber768)));
+    assert!(info.recipients.contains(&Some(EncapsulationScheme::X25519)));
+    assert_eq!(info.nonce_size, 12);
+    assert!(info.has_digest);
+    assert!(info.kdf.is_none());
+
+    let sealed_message = encrypted.assertions_with_predicate(known_values::HAS_RECIPIENT)[0].try_object()?;
+    let elided = encrypted.elide_removing_target(&sealed_message);
+    let info = api::encrypt::encryption_info(&elided)?.unwrap();
+    assert_eq!(info.recipients.iter().filter(|scheme| scheme.is_none()).count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_api_sskr() -> Result<()> {
+    let envelope = alice_knows_bob();
+    let spec = SSKRSpec::new(1, vec![SSKRGroupSpec::new(2, 3)?])?;
+    let groups = api::sskr::split(&envelope, &spec, None, &[], &[])?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].len(), 3);
+
+    let restored = api::sskr::join(&groups[0][1..])?;
+    assert_eq!(restored.ur_string(), ALICE_KNOWS_BOB_EXAMPLE);
+    assert!(api::sskr::join(&groups[0][..1]).is_err());
+
+    let bob = PrivateKeyBase::from_ur_string(BOB_PRVKEY_BASE)?;
+    let share_recipient = api::sskr::ShareRecipient {
+        group: 0,
+        member: Some(1),
+        holder: Envelope::new("Bob"),
+        public_keys: vec![PublicKeys::from_ur_string(BOB_PUBKEYS)?],
+    };
+    let groups = api::sskr::split(&envelope, &spec, None, &[], &[share_recipient])?;
+    assert!(groups[0][1].subject().is_encrypted());
+    assert_eq!(groups[0][1].extract_object_for_predicate::<String>(known_values::HOLDER)?, "Bob");
+    let bob_share = api::encrypt::decrypt_to_recipient(&groups[0][1], &bob)?;
+    let restored = api::sskr::join(&[groups[0][0].clone(), bob_share])?;
+    assert_eq!(restored.ur_string(), ALICE_KNOWS_BOB_EXAMPLE);
+    Ok(())
+}
+
+#[test]
+fn test_api_proof() -> Result<()> {
+    let envelope = alice_knows_bob();
+    let assertion = api::assertion::find_by_predicate(&envelope, &Envelope::new("knows"));
+    assert_eq!(assertion.len(), 1);
+
+    let target: HashSet<Digest> = [assertion[0].digest().into_owned()].into_iter().collect();
+    let elided = api::elide::elide_revealing(&envelope, &HashSet::new(), &ObscureAction::Elide);
+    let proof = api::proof::create(&envelope, &target)?;
+    api::proof::confirm(&elided, &proof, &target)?;
+    api::proof::confirm_digest(&envelope.digest(), &proof, &target)?;
+    Ok(())
+}
+
+#[test]
+fn test_api_xid() -> Result<()> {
+    bc_envelope::register_tags();
+    let alice = PrivateKeyBase::from_ur_string(ALICE_PRVKEY_BASE)?;
+    let bob_public = PublicKeys::from_ur_string(BOB_PUBKEYS)?;
+
+    let mut xid_document = api::xid::new_document(
+        &api::xid::InputKey::Private(alice),
+        &api::xid::KeyOptions { name: "Alice".to_string(), ..Default::default() },
+    )?;
+    api::xid::add_key(
+        &mut xid_document,
+        &api::xid::InputKey::Public(bob_public.clone()),
+        &api::xid::KeyOptions { name: "Bob".to_string(), permissions: vec![Privilege::Sign], ..Default::default() },
+    )?;
+    assert_eq!(xid_document.keys().len(), 2);
+
+    api::xid::update_key(
+        &mut xid_document,
+        &bob_public,
+        &api::xid::KeyOptions { name: "Robert".to_string(), ..Default::default() },
+    )?;
+    let bob_key = xid_document.find_key_by_public_keys(&bob_public).unwrap();
+    assert_eq!(bob_key.name(), "Robert");
+    Ok(())
+}
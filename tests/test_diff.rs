@@ -0,0 +1,89 @@
+use indoc::indoc;
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+const BOB_DIGEST: &str = "ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl";
+
+#[test]
+fn test_diff_identical() -> Result<()> {
+    run_cli_expect(&["diff", ALICE_KNOWS_BOB_EXAMPLE, ALICE_KNOWS_BOB_EXAMPLE], "")
+}
+
+#[test]
+fn test_diff_assertions() -> Result<()> {
+    let e1 = run_cli(&["assertion", "remove", "pred-obj", "string", "knows", "string", "Bob", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let e2 = run_cli(&["assertion", "add", "pred-obj", "string", "knows", "string", "Carol", &e1])?;
+    let e3 = run_cli(&["assertion", "add", "pred-obj", "known", "isA", "string", "Person", &e2])?;
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, &e3],
+        indoc!(r#"
+        changed /"knows" "Bob" -> "Carol"
+        added / 'isA': "Person"
+        "#)
+    )?;
+    run_cli_expect(
+        &["diff", &e3, ALICE_KNOWS_BOB_EXAMPLE],
+        indoc!(r#"
+        changed /"knows" "Carol" -> "Bob"
+        removed / 'isA': "Person"
+        "#)
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_obscured() -> Result<()> {
+    let elided = run_cli(&["elide", "removing", BOB_DIGEST, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, &elided],
+        r#"elided /"knows" "Bob""#
+    )?;
+    run_cli_expect(
+        &["diff", &elided, ALICE_KNOWS_BOB_EXAMPLE],
+        r#"revealed /"knows" "Bob""#
+    )?;
+    let compressed = run_cli(&["compress", "--subject", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, &compressed],
+        r#"compressed / "Alice""#
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_json() -> Result<()> {
+    let elided = run_cli(&["elide", "removing", BOB_DIGEST, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["diff", "--type", "json", ALICE_KNOWS_BOB_EXAMPLE, &elided],
+        indoc!(r#"
+        [
+          {
+            "kind": "elided",
+            "path": [
+              "\"knows\""
+            ],
+            "old": {
+              "digest": "ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl",
+              "summary": "\"Bob\""
+            },
+            "new": {
+              "digest": "ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl",
+              "summary": "ELIDED"
+            }
+          }
+        ]
+        "#)
+    )
+}
+
+#[test]
+fn test_diff_stdin() -> Result<()> {
+    let e1 = run_cli(&["assertion", "add", "pred-obj", "string", "age", "number", "30", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect_stdin(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"added / "age": 30"#,
+        &e1
+    )
+}
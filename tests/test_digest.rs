@@ -1,3 +1,5 @@
+use indoc::indoc;
+
 mod common;
 use common::*;
 
@@ -18,3 +20,38 @@ fn test_envelope_digest_hex() -> Result<()> {
         "8955db5e016affb133df56c11fe6c5c82fa3036263d651286d134c7e56c0e9f2"
     )
 }
+
+#[test]
+fn test_envelope_digest_annotate() -> Result<()> {
+    run_cli_expect(
+        &["digest", "--depth", "deep", "--annotate", ALICE_KNOWS_BOB_EXAMPLE],
+        indoc!(r#"
+        ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds / node "Alice" [ "knows": "Bob" ]
+        ur:digest/hdcxbwmwcwfdkecauerfvsdirpwpfhfgtalfmulesnstvlrpoyfzuyenamdpmdcfutdlstyaqzrk /subj leaf "Alice"
+        ur:digest/hdcxkstbiywmmygsasktnbfwhtrppkclwdcmmugejesokejlbnftrdwspsmdcechbboerhzebtws /"knows" assertion "knows": "Bob"
+        ur:digest/hdcxuykitdcegyinqzlrlgdrcwsbbkihcemtchsntabdpldtbzjepkwsrkdrlernykrddpjtgdfh /"knows"/pred leaf "knows"
+        ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl /"knows"/obj leaf "Bob"
+        "#)
+    )?;
+    run_cli_expect(
+        &["digest", "--annotate", "--hex", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"8955db5e016affb133df56c11fe6c5c82fa3036263d651286d134c7e56c0e9f2 / node "Alice" [ "knows": "Bob" ]"#
+    )
+}
+
+#[test]
+fn test_envelope_digest_annotate_wrapped() -> Result<()> {
+    let annotated = run_cli(&["digest", "--depth", "shallow", "--annotate", "--hex", CREDENTIAL_EXAMPLE])?;
+    let paths = annotated.lines()
+        .map(|line| line.split(' ').skip(1).take(2).collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>();
+    assert_eq!(paths, [
+        "/ node",
+        "/subj wrapped",
+        "/'signed' assertion",
+        "/'note' assertion",
+    ]);
+    let deep = run_cli(&["digest", "--depth", "deep", "--annotate", CREDENTIAL_EXAMPLE])?;
+    assert!(deep.lines().any(|line| line.ends_with(r#" /subj/wrapped/"lastName"/obj leaf "Maxwell""#)));
+    Ok(())
+}
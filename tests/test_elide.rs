@@ -53,3 +53,86 @@ fn test_elide_2() -> Result<()> {
     )?;
     Ok(())
 }
+
+#[test]
+fn test_elide_selectors() -> Result<()> {
+    run_cli_expect(
+        &["format", &run_cli(&["elide", "removing", "--predicate", "knows", ALICE_KNOWS_BOB_EXAMPLE])?],
+        indoc!(r#"
+        "Alice" [
+            ELIDED
+        ]
+        "#)
+    )?;
+    run_cli_expect(
+        &["format", &run_cli_stdin(&["elide", "removing", "--object", "Bob"], ALICE_KNOWS_BOB_EXAMPLE)?],
+        indoc!(r#"
+        "Alice" [
+            "knows": ELIDED
+        ]
+        "#)
+    )?;
+    run_cli_expect(
+        &["format", &run_cli(&["elide", "revealing", "--object", "\"Bob\"", ALICE_KNOWS_BOB_EXAMPLE])?],
+        indoc!(r#"
+        ELIDED [
+            "knows": "Bob"
+        ]
+        "#)
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_elide_selectors_nested() -> Result<()> {
+    let elided = run_cli(&["elide", "revealing", "--query", "{}.lastName", "--predicate", "firstName", CREDENTIAL_EXAMPLE])?;
+    run_cli_expect(
+        &["format", &elided],
+        indoc!(r#"
+        {
+            ELIDED [
+                "firstName": "James"
+                "lastName": "Maxwell"
+                ELIDED (11)
+            ]
+        } [
+            ELIDED (2)
+        ]
+        "#)
+    )?;
+    let elided = run_cli(&["elide", "removing", "--predicate", "'issuer'", "--predicate", "photo", "--object", "15", CREDENTIAL_EXAMPLE])?;
+    let formatted = run_cli(&["format", &elided])?;
+    assert!(formatted.contains(r#""professionalDevelopmentHours": ELIDED"#));
+    assert!(formatted.contains("ELIDED (2)"));
+    assert!(!formatted.contains("photo"));
+    assert_eq!(run_cli(&["digest", &elided])?, run_cli(&["digest", CREDENTIAL_EXAMPLE])?);
+    Ok(())
+}
+
+#[test]
+fn test_elide_selectors_and_target() -> Result<()> {
+    let bob_digest = run_cli(&["digest", &run_cli(&["subject", "type", "string", "Bob"])?])?;
+    let alice_digest = run_cli(&["digest", &run_cli(&["subject", "type", "string", "Alice"])?])?;
+    let elided = run_cli(&["elide", "removing", "--object", "Bob", &alice_digest, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["format", &elided],
+        indoc!(r#"
+        ELIDED [
+            "knows": ELIDED
+        ]
+        "#)
+    )?;
+    assert!(run_cli(&["elide", "removing", "--object", "Bob", &bob_digest]).is_err());
+
+    // With the envelope on stdin, a single list of digests is the target.
+    let elided = run_cli_stdin(&["elide", "removing", "--object", "Bob", &alice_digest], ALICE_KNOWS_BOB_EXAMPLE)?;
+    run_cli_expect(
+        &["format", &elided],
+        indoc!(r#"
+        ELIDED [
+            "knows": ELIDED
+        ]
+        "#)
+    )?;
+    Ok(())
+}
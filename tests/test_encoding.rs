@@ -0,0 +1,96 @@
+use anyhow::Result;
+use assert_cmd::Command;
+
+mod common;
+use common::*;
+
+const HELLO_HEX: &str = "d8c8d8c96648656c6c6f2e";
+const HELLO_BYTEWORDS: &str = "trip soap trip solo inky fund inch jazz jazz jowl drum down jury fizz safe";
+
+#[test]
+fn test_encoding_out() -> Result<()> {
+    run_cli_expect(&["--out", "hex", "subject", "type", "string", HELLO_STR], HELLO_HEX)?;
+    run_cli_expect(&["subject", "type", "string", HELLO_STR, "--out", "bytewords"], HELLO_BYTEWORDS)
+}
+
+#[test]
+fn test_encoding_in() -> Result<()> {
+    run_cli_expect(&["--in", "hex", "format", HELLO_HEX], r#""Hello.""#)?;
+    run_cli_expect_stdin(&["--in", "bytewords", "format"], r#""Hello.""#, HELLO_BYTEWORDS)?;
+    run_cli_expect(&["--in", "hex", "--out", "ur", "digest", HELLO_HEX], &run_cli(&["digest", HELLO_ENVELOPE_UR])?)
+}
+
+#[test]
+fn test_encoding_pipeline() -> Result<()> {
+    run_cli_piped_expect(
+        &[
+            &["--out", "hex", "subject", "type", "string", "Alice"],
+            &["--in", "hex", "--out", "bytewords", "assertion", "add", "pred-obj", "string", "knows", "string", "Bob"],
+            &["--in", "bytewords", "format", "--type", "ur"],
+        ],
+        ALICE_KNOWS_BOB_EXAMPLE
+    )
+}
+
+#[test]
+fn test_encoding_binary() -> Result<()> {
+    let output = Command::cargo_bin("envelope").unwrap()
+        .args(["--out", "binary", "subject", "type", "string", HELLO_STR])
+        .assert()
+        .success();
+    let binary = output.get_output().stdout.clone();
+    assert_eq!(hex::encode(&binary), HELLO_HEX);
+
+    let output = Command::cargo_bin("envelope").unwrap()
+        .args(["--in", "binary", "format", "--type", "ur"])
+        .write_stdin(binary)
+        .assert()
+        .success();
+    assert_eq!(String::from_utf8(output.get_output().stdout.clone())?.trim(), HELLO_ENVELOPE_UR);
+    Ok(())
+}
+
+#[test]
+fn test_encoding_out_envelopes() -> Result<()> {
+    // Each envelope of a command that produces several is encoded.
+    let assertions = run_cli(&["--out", "hex", "assertion", "all", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let expected = run_cli(&["assertion", "all", ALICE_KNOWS_BOB_EXAMPLE])?
+        .lines()
+        .map(|assertion| run_cli(&["--out", "hex", "extract", "envelope", assertion]))
+        .collect::<Result<Vec<_>>>()?
+        .join("\n");
+    assert_eq!(assertions, expected);
+    assert!(run_cli(&["--out", "binary", "assertion", "all", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_encoding_binary_requires_single_envelope() {
+    assert!(run_cli(&["--out", "binary", "format", HELLO_ENVELOPE_UR]).is_err());
+}
+
+#[test]
+fn test_encoding_out_text() -> Result<()> {
+    // Only envelope results are re-encoded; text is written as is.
+    let wrapped = run_cli(&["subject", "type", "wrapped", HELLO_ENVELOPE_UR])?;
+    run_cli_expect(&["--out", "hex", "extract", "wrapped", &wrapped], HELLO_HEX)?;
+    run_cli_expect(&["--out", "hex", "format", "--type", "ur", HELLO_ENVELOPE_UR], HELLO_ENVELOPE_UR)
+}
+
+#[test]
+fn test_encoding_in_arguments() -> Result<()> {
+    let alice = run_cli(&["--out", "hex", "subject", "type", "string", "Alice"])?;
+    let assertion = run_cli(&["--out", "hex", "subject", "assertion", "string", "knows", "string", "Bob"])?;
+    let added = run_cli(&["--in", "hex", "assertion", "add", "envelope", &assertion, &alice])?;
+    assert_eq!(added, ALICE_KNOWS_BOB_EXAMPLE);
+    let added = run_cli(&["--in", "hex", "--out", "hex", "assertion", "add", "envelope", &assertion, &alice])?;
+    run_cli_expect(&["--in", "hex", "--out", "hex", "assertion", "remove", "envelope", &assertion, &added], &alice)?;
+
+    let shares = run_cli(&["--out", "hex", "sskr", "split", "--group", "2-of-3", HELLO_ENVELOPE_UR])?;
+    let shares = shares.split(' ').collect::<Vec<_>>();
+    assert_eq!(shares.len(), 3);
+    assert!(shares.iter().all(|share| hex::decode(share).is_ok()));
+    run_cli_expect(&["--in", "hex", "sskr", "join", shares[0], shares[2]], HELLO_ENVELOPE_UR)?;
+    assert!(run_cli(&["sskr", "join", "not-a-share"]).is_err());
+    Ok(())
+}
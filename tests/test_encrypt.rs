@@ -27,3 +27,215 @@ fn test_encrypt() {
     ]).unwrap();
     assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
 }
+
+#[test]
+fn test_encrypt_password() {
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--password=Correct-Horse",
+        ALICE_KNOWS_BOB_EXAMPLE
+    ]).unwrap();
+    run_cli_expect(
+        &["format", &encrypted],
+        indoc!(r#"
+        ENCRYPTED [
+            "kdf": "Argon2id" [
+                "iterations": 2
+                "memory": 19456
+                "parallelism": 1
+                'salt': Salt
+            ]
+            "knows": "Bob"
+        ]
+        "#)
+    ).unwrap();
+    let decrypted = run_cli(&["decrypt", "--password=Correct-Horse", &encrypted]).unwrap();
+    assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
+    assert!(run_cli(&["decrypt", "--password=Battery-Staple", &encrypted]).is_err());
+    assert!(run_cli(&["decrypt", "--password=Correct-Horse", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    assert!(run_cli(&["encrypt", "--password=Correct-Horse", "--key", KEY_EXAMPLE, ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+}
+
+#[test]
+fn test_elide_encrypt_password() {
+    let encrypted = run_cli(&[
+        "elide",
+        "removing",
+        "--action",
+        "encrypt",
+        "--password=Correct-Horse",
+        "--object",
+        "Bob",
+        ALICE_KNOWS_BOB_EXAMPLE
+    ]).unwrap();
+    run_cli_expect(
+        &["format", &encrypted],
+        indoc!(r#"
+        "Alice" [
+            "knows": ENCRYPTED [
+                "kdf": "Argon2id" [
+                    "iterations": 2
+                    "memory": 19456
+                    "parallelism": 1
+                    'salt': Salt
+                ]
+            ]
+        ]
+        "#)
+    ).unwrap();
+    let decrypted = run_cli(&["decrypt", "--password=Correct-Horse", &encrypted]).unwrap();
+    assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
+    assert!(run_cli(&["decrypt", "--password=Battery-Staple", &encrypted]).is_err());
+
+    // The elements can still be decrypted after the envelope is signed and wrapped.
+    let wrapped = run_cli_piped(&[
+        &["sign", "--signer", ALICE_PRVKEY_BASE, &encrypted],
+        &["subject", "type", "wrapped"],
+    ]).unwrap();
+    let decrypted = run_cli(&["decrypt", "--password=Correct-Horse", &wrapped]).unwrap();
+    run_cli_expect(
+        &["format", &decrypted],
+        indoc!(r#"
+        {
+            "Alice" [
+                "knows": "Bob"
+                'signed': Signature
+            ]
+        }
+        "#)
+    ).unwrap();
+    run_cli_piped(&[
+        &["extract", "wrapped", &decrypted],
+        &["verify", "--verifier", ALICE_PUBKEYS],
+    ]).unwrap();
+}
+
+#[test]
+fn test_decrypt_candidate_recipients() {
+    let encrypted = run_cli(&["encrypt", "--recipient", BOB_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE]).unwrap();
+
+    run_cli_expect(
+        &["decrypt", "--report-key", "--recipient", ALICE_PRVKEY_BASE, "--recipient", BOB_PRVKEY_BASE, &encrypted],
+        "PublicKeys(e2c18423)"
+    ).unwrap();
+    assert!(run_cli(&["decrypt", "--report-key", "--key", KEY_EXAMPLE, &encrypted]).is_err());
+    let decrypted = run_cli(&["decrypt", "--recipient", ALICE_PRVKEY_BASE, "--recipient", BOB_PRVKEY_BASE, &encrypted]).unwrap();
+    assert_eq!(run_cli(&["digest", &decrypted]).unwrap(), run_cli(&["digest", &encrypted]).unwrap());
+    run_cli_expect(&["extract", "string", &decrypted], "Alice").unwrap();
+
+    assert!(run_cli(&["decrypt", "--recipient", ALICE_PRVKEY_BASE, "--recipient", CAROL_PRVKEY_BASE, &encrypted]).is_err());
+}
+
+#[test]
+fn test_decrypt_xid_document_recipient() {
+    let encrypted = run_cli(&["encrypt", "--recipient", BOB_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE]).unwrap();
+    let xid_document = run_cli_piped(&[
+        &["xid", "new", "--private", "include", "--name", "Alice", ALICE_PRVKEY_BASE],
+        &["xid", "key", "add", "--private", "include", "--name", "Bob", BOB_PRVKEY_BASE],
+    ]).unwrap();
+
+    let decrypted = run_cli(&["decrypt", "--recipient", &xid_document, &encrypted]).unwrap();
+    run_cli_expect(&["extract", "string", &decrypted], "Alice").unwrap();
+    run_cli_expect(
+        &["decrypt", "--report-key", "--recipient", &xid_document, &encrypted],
+        r#"PublicKeys(e2c18423) "Bob" of XID(93a4d4e7)"#
+    ).unwrap();
+
+    // Keys without the Encrypt privilege are not tried.
+    let signing_only = run_cli_piped(&[
+        &["xid", "new", "--private", "include", "--name", "Alice", ALICE_PRVKEY_BASE],
+        &["xid", "key", "add", "--private", "include", "--name", "Bob", "--allow", "sign", BOB_PRVKEY_BASE],
+    ]).unwrap();
+    assert!(run_cli(&["decrypt", "--recipient", &signing_only, &encrypted]).is_err());
+}
+
+#[test]
+fn test_encrypt_add_remove_recipient() {
+    let signed = run_cli_piped(&[
+        &["subject", "type", "wrapped", ALICE_KNOWS_BOB_EXAMPLE],
+        &["encrypt", "--recipient", ALICE_PUBKEYS],
+        &["sign", "--signer", ALICE_PRVKEY_BASE],
+    ]).unwrap();
+
+    // Add Bob using Alice's private key to recover the content key.
+    let added = run_cli(&["encrypt", "add-recipient", "--existing", ALICE_PRVKEY_BASE, BOB_PUBKEYS, &signed]).unwrap();
+    run_cli_expect(
+        &["format", &added],
+        indoc!(r#"
+        ENCRYPTED [
+            'hasRecipient': SealedMessage
+            'hasRecipient': SealedMessage
+            'signed': Signature
+        ]
+        "#)
+    ).unwrap();
+    run_cli(&["verify", "--verifier", ALICE_PUBKEYS, &added]).unwrap();
+    run_cli(&["decrypt", "--recipient", BOB_PRVKEY_BASE, &added]).unwrap();
+
+    // Carol is not a recipient, so she cannot add anyone.
+    assert!(run_cli(&["encrypt", "add-recipient", "--existing", CAROL_PRVKEY_BASE, CAROL_PUBKEYS, &signed]).is_err());
+
+    // Remove Alice by her private key.
+    let removed = run_cli(&["encrypt", "remove-recipient", ALICE_PRVKEY_BASE, &added]).unwrap();
+    run_cli(&["verify", "--verifier", ALICE_PUBKEYS, &removed]).unwrap();
+    assert!(run_cli(&["decrypt", "--recipient", ALICE_PRVKEY_BASE, &removed]).is_err());
+    run_cli(&["decrypt", "--recipient", BOB_PRVKEY_BASE, &removed]).unwrap();
+
+    // Remove Bob by the digest of his assertion.
+    let assertion = run_cli(&["assertion", "find", "predicate", "known", "hasRecipient", &removed]).unwrap();
+    let digest = run_cli(&["digest", &assertion]).unwrap();
+    run_cli_expect(
+        &["format", &run_cli(&["encrypt", "remove-recipient", &digest, &removed]).unwrap()],
+        indoc!(r#"
+        ENCRYPTED [
+            'signed': Signature
+        ]
+        "#)
+    ).unwrap();
+}
+
+#[test]
+fn test_encrypt_add_recipient_with_key() {
+    let encrypted = run_cli(&["encrypt", "--key", KEY_EXAMPLE, ALICE_KNOWS_BOB_EXAMPLE]).unwrap();
+    let added = run_cli(&["encrypt", "add-recipient", "--key", KEY_EXAMPLE, BOB_PUBKEYS, &encrypted]).unwrap();
+    run_cli(&["decrypt", "--recipient", BOB_PRVKEY_BASE, &added]).unwrap();
+
+    let wrong_key = run_cli(&["generate", "key"]).unwrap();
+    assert!(run_cli(&["encrypt", "add-recipient", "--key", &wrong_key, BOB_PUBKEYS, &encrypted]).is_err());
+    assert!(run_cli(&["encrypt", "add-recipient", "--key", KEY_EXAMPLE, BOB_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+}
+
+#[test]
+fn test_encrypt_info() {
+    let encrypted = run_cli(&["encrypt", "--recipient", BOB_PUBKEYS, "--recipient", CAROL_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE]).unwrap();
+    run_cli_expect(
+        &["info", &encrypted],
+        indoc!(r#"
+        Format: ur:envelope
+        Description: Gordian Envelope
+        Encryption: ChaCha20-Poly1305
+        Nonce Size: 12 bytes
+        Subject Digest: present
+        Recipients: 2
+        Recipient 1: X25519
+        Recipient 2: X25519
+        "#)
+    ).unwrap();
+
+    let encrypted = run_cli(&["encrypt", "--password=Opening Sesame", ALICE_KNOWS_BOB_EXAMPLE]).unwrap();
+    run_cli_expect_stdin(
+        &["info"],
+        indoc!(r#"
+        Format: ur:envelope
+        Description: Gordian Envelope
+        Encryption: ChaCha20-Poly1305
+        Nonce Size: 12 bytes
+        Subject Digest: present
+        Key Derivation: Argon2id
+        Recipients: 0
+        "#),
+        &format!("{}\n", encrypted),
+    ).unwrap();
+
+    run_cli_expect(&["info", ALICE_KNOWS_BOB_EXAMPLE], "Format: ur:envelope\nDescription: Gordian Envelope").unwrap();
+}
@@ -233,3 +233,130 @@ fn test_format_tree() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_format_json() -> Result<()> {
+    let output = run_cli_raw(&["format", "--type", "json", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert_eq!(
+        output,
+        indoc! {r#"
+        {
+          "case": "node",
+          "digest": "ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds",
+          "subject": {
+            "case": "leaf",
+            "digest": "ur:digest/hdcxbwmwcwfdkecauerfvsdirpwpfhfgtalfmulesnstvlrpoyfzuyenamdpmdcfutdlstyaqzrk",
+            "cbor": "65416c696365",
+            "value": "Alice",
+            "summary": "\"Alice\""
+          },
+          "assertions": [
+            {
+              "case": "assertion",
+              "digest": "ur:digest/hdcxkstbiywmmygsasktnbfwhtrppkclwdcmmugejesokejlbnftrdwspsmdcechbboerhzebtws",
+              "predicate": {
+                "case": "leaf",
+                "digest": "ur:digest/hdcxuykitdcegyinqzlrlgdrcwsbbkihcemtchsntabdpldtbzjepkwsrkdrlernykrddpjtgdfh",
+                "cbor": "656b6e6f7773",
+                "value": "knows",
+                "summary": "\"knows\""
+              },
+              "object": {
+                "case": "leaf",
+                "digest": "ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl",
+                "cbor": "63426f62",
+                "value": "Bob",
+                "summary": "\"Bob\""
+              }
+            }
+          ]
+        }
+        "#}
+    );
+    Ok(())
+}
+
+#[test]
+fn test_format_json_obscured() -> Result<()> {
+    let output = run_cli(&["format", "--type", "json", ENVELOPE])?;
+    assert!(output.contains(r#""case": "elided""#));
+    assert!(output.contains(r#""case": "knownValue""#));
+    assert!(output.contains(r#""case": "wrapped""#));
+    assert!(output.contains(r#""name": "isA""#));
+    Ok(())
+}
+
+#[test]
+fn test_format_multipart() -> Result<()> {
+    let parts = run_cli(&["format", "--type", "multipart", "--max-fragment-len", "200", CREDENTIAL_EXAMPLE])?;
+    let lines = parts.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("ur:envelope/1-3/"));
+    assert!(lines[2].starts_with("ur:envelope/3-3/"));
+
+    // Parts may be read from stdin, one per line, or passed as a single argument.
+    run_cli_expect_stdin(&["format", "--type", "ur"], CREDENTIAL_EXAMPLE, &parts)?;
+    run_cli_expect(&["format", "--type", "ur", &lines.join(" ")], CREDENTIAL_EXAMPLE)?;
+
+    // An incomplete set of parts is rejected.
+    assert!(run_cli(&["format", "--type", "ur", lines[0]]).is_err());
+    Ok(())
+}
+
+/// "Alice" knows "Bob", with the subject encrypted and "Bob" compressed.
+fn obscured_alice_knows_bob() -> Result<String> {
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let encrypted = run_cli(&["encrypt", "--key", KEY_EXAMPLE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli(&["elide", "removing", "--action", "compress", &bob, &encrypted])
+}
+
+#[test]
+fn test_format_dot() -> Result<()> {
+    let elided = run_cli(&["elide", "removing", "ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["format", "--type", "dot", &elided],
+        indoc!(r#"
+        digraph envelope {
+            rankdir=LR;
+            node [shape=box, fontname="Menlo"];
+            n0 [label="node 8955db5e"];
+            n1 [label="leaf 13941b48\n\"Alice\""];
+            n2 [label="assertion 78d666eb"];
+            n3 [label="leaf db7dd21c\n\"knows\""];
+            n4 [label="elided 13b74194", style=dashed, color=gray50, fontcolor=gray50];
+            n0 -> n1 [label="subj"];
+            n0 -> n2;
+            n2 -> n3 [label="pred"];
+            n2 -> n4 [label="obj"];
+        }
+        "#)
+    )?;
+
+    let output = run_cli(&["format", "--type", "dot", &obscured_alice_knows_bob()?])?;
+    assert!(output.contains(r#"n1 [label="encrypted 13941b48", style=filled, fillcolor=lightpink];"#));
+    assert!(output.contains(r#"n4 [label="compressed 13b74194", style=filled, fillcolor=lightblue];"#));
+    Ok(())
+}
+
+#[test]
+fn test_format_mermaid() -> Result<()> {
+    run_cli_expect(
+        &["format", "--type", "mermaid", &obscured_alice_knows_bob()?],
+        indoc!(r#"
+        graph LR
+            n0["node 8955db5e"]
+            n1["encrypted 13941b48"]
+            n2["assertion 78d666eb"]
+            n3["leaf db7dd21c<br>#quot;knows#quot;"]
+            n4["compressed 13b74194"]
+            n0 -->|subj| n1
+            n0 --> n2
+            n2 -->|pred| n3
+            n2 -->|obj| n4
+            classDef encrypted fill:#ffb6c1
+            class n1 encrypted
+            classDef compressed fill:#add8e6
+            class n4 compressed
+        "#)
+    )
+}
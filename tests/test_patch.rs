@@ -0,0 +1,87 @@
+use indoc::indoc;
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_patch_assertions() -> Result<()> {
+    let e1 = run_cli(&["assertion", "remove", "pred-obj", "string", "knows", "string", "Bob", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let e2 = run_cli(&["assertion", "add", "pred-obj", "string", "knows", "string", "Carol", &e1])?;
+    let modified = run_cli(&["assertion", "add", "pred-obj", "known", "isA", "string", "Person", &e2])?;
+    let patch = run_cli(&["patch", "create", ALICE_KNOWS_BOB_EXAMPLE, &modified])?;
+    run_cli_expect(
+        &["format", &patch],
+        indoc!(r#"
+        Digest(8955db5e) [
+            "add": {
+                "knows": "Carol"
+            }
+            "add": {
+                'isA': "Person"
+            }
+            "remove": Digest(78d666eb)
+            "result": Digest(7257ee9a)
+        ]
+        "#)
+    )?;
+    let patched = run_cli(&["patch", "apply", &patch, ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert_eq!(patched, modified);
+    Ok(())
+}
+
+#[test]
+fn test_patch_nested() -> Result<()> {
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let bob = run_cli(&["assertion", "add", "pred-obj", "string", "age", "number", "30", &bob])?;
+    let e1 = run_cli(&["assertion", "remove", "pred-obj", "string", "knows", "string", "Bob", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let modified = run_cli(&["assertion", "add", "pred-obj", "string", "knows", "envelope", &bob, &e1])?;
+    let patch = run_cli(&["patch", "create", ALICE_KNOWS_BOB_EXAMPLE, &modified])?;
+    run_cli_expect(
+        &["format", &patch],
+        indoc!(r#"
+        Digest(8955db5e) [
+            "add": {
+                "age": 30
+            } [
+                "path": [Digest(78d666eb), Digest(13b74194)]
+            ]
+            "result": Digest(77ab7d85)
+        ]
+        "#)
+    )?;
+    run_cli_expect_stdin(
+        &["patch", "apply", &patch],
+        &modified,
+        ALICE_KNOWS_BOB_EXAMPLE
+    )
+}
+
+#[test]
+fn test_patch_signed() -> Result<()> {
+    let original = run_cli(&["subject", "type", "wrapped", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let original = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, &original])?;
+    let e1 = run_cli(&["assertion", "add", "pred-obj", "string", "age", "number", "30", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let e2 = run_cli(&["subject", "type", "wrapped", &e1])?;
+    let modified = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, &e2])?;
+
+    let patch = run_cli(&["patch", "create", &original, &modified])?;
+    let wrapped_patch = run_cli(&["subject", "type", "wrapped", &patch])?;
+    let signed_patch = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, &wrapped_patch])?;
+    run_cli(&["verify", "--verifier", ALICE_PUBKEYS, &signed_patch])?;
+    let patched = run_cli(&["patch", "apply", &signed_patch, &original])?;
+    assert_eq!(patched, modified);
+    run_cli(&["verify", "--verifier", ALICE_PUBKEYS, &patched])?;
+    Ok(())
+}
+
+#[test]
+fn test_patch_errors() -> Result<()> {
+    let modified = run_cli(&["assertion", "add", "pred-obj", "string", "age", "number", "30", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let patch = run_cli(&["patch", "create", ALICE_KNOWS_BOB_EXAMPLE, &modified])?;
+    assert!(run_cli(&["patch", "apply", &patch, &modified]).is_err());
+
+    let carol = run_cli(&["subject", "type", "string", "Carol"])?;
+    assert!(run_cli(&["patch", "create", ALICE_KNOWS_BOB_EXAMPLE, &carol]).is_err());
+    Ok(())
+}
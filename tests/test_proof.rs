@@ -0,0 +1,27 @@
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_proof_confirm() -> Result<()> {
+    let bob_digest = run_cli(&["digest", &run_cli(&["subject", "type", "string", "Bob"])?])?;
+    let proof = run_cli(&["proof", "create", &bob_digest, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(&["proof", "confirm", &proof, &bob_digest, ALICE_KNOWS_BOB_EXAMPLE], ALICE_KNOWS_BOB_EXAMPLE)?;
+    run_cli_expect(&["proof", "confirm", "--silent", &proof, &bob_digest, ALICE_KNOWS_BOB_EXAMPLE], "")?;
+    Ok(())
+}
+
+#[test]
+fn test_proof_confirm_digest() -> Result<()> {
+    let root = run_cli(&["digest", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let bob_digest = run_cli(&["digest", &run_cli(&["subject", "type", "string", "Bob"])?])?;
+    let proof = run_cli(&["proof", "create", &bob_digest, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(&["proof", "confirm", &proof, &bob_digest, &root], &root)?;
+
+    let carol_digest = run_cli(&["digest", &run_cli(&["subject", "type", "string", "Carol"])?])?;
+    assert!(run_cli(&["proof", "confirm", &proof, &carol_digest, &root]).is_err());
+    let other_root = run_cli(&["digest", &run_cli(&["subject", "type", "string", "Alice"])?])?;
+    assert!(run_cli(&["proof", "confirm", &proof, &bob_digest, &other_root]).is_err());
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_qr_text() -> Result<()> {
+    let output = run_cli(&["qr", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(output.contains('█'));
+    assert!(output.lines().count() > 10);
+    Ok(())
+}
+
+#[test]
+fn test_qr_svg() -> Result<()> {
+    let output = run_cli(&["qr", "--type", "svg", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(output.starts_with("<?xml"));
+    assert!(output.contains("<svg"));
+    Ok(())
+}
+
+#[test]
+fn test_qr_png() -> Result<()> {
+    let dir = TempPath::dir("qr-png");
+    let path = dir.join("alice.png");
+    run_cli_expect(&["qr", "--type", "png", "--output", path.to_str().unwrap(), ALICE_KNOWS_BOB_EXAMPLE], path.to_str().unwrap())?;
+    assert!(std::fs::read(&path)?.starts_with(b"\x89PNG"));
+    assert!(run_cli(&["qr", "--type", "png", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_qr_multipart() -> Result<()> {
+    let dir = TempPath::dir("qr-multipart");
+    let path = dir.join("alice.png");
+    let output = run_cli(&["qr", "--type", "png", "--output", path.to_str().unwrap(), "--max-fragment-len", "10", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let paths = output.lines().collect::<Vec<_>>();
+    assert_eq!(paths.len(), 3);
+    assert!(paths[0].ends_with("alice-1.png"));
+    for path in paths {
+        assert!(std::fs::metadata(path)?.is_file());
+    }
+
+    // The parts of a multipart UR are rendered one frame per part.
+    let parts = run_cli(&["format", "--type", "multipart", "--max-fragment-len", "10", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let svg = run_cli_stdin(&["qr", "--type", "svg"], &parts)?;
+    assert_eq!(svg.matches("<svg").count(), 3);
+
+    let gif = dir.join("alice.gif");
+    run_cli(&["qr", "--type", "gif", "--output", gif.to_str().unwrap(), "--max-fragment-len", "10", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(std::fs::read(&gif)?.starts_with(b"GIF89a"));
+    Ok(())
+}
+
+#[test]
+fn test_qr_read_image() -> Result<()> {
+    let dir = TempPath::dir("qr-read");
+    let png = dir.join("alice.png");
+    run_cli(&["qr", "--type", "png", "--output", png.to_str().unwrap(), ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(&["format", "--type", "ur", png.to_str().unwrap()], ALICE_KNOWS_BOB_EXAMPLE)?;
+    run_cli_expect(&["info", png.to_str().unwrap()], "Format: ur:envelope\nDescription: Gordian Envelope")?;
+
+    let jpeg = dir.join("alice.jpg");
+    image::open(&png)?.save(&jpeg)?;
+    run_cli_expect(&["format", "--type", "ur", jpeg.to_str().unwrap()], ALICE_KNOWS_BOB_EXAMPLE)?;
+    Ok(())
+}
+
+#[test]
+fn test_qr_read_multipart() -> Result<()> {
+    let dir = TempPath::dir("qr-read-multipart");
+    let frames = dir.join("frames");
+    std::fs::create_dir_all(&frames)?;
+    let png = frames.join("alice.png");
+    run_cli(&["qr", "--type", "png", "--output", png.to_str().unwrap(), "--max-fragment-len", "10", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(&["format", "--type", "ur", frames.to_str().unwrap()], ALICE_KNOWS_BOB_EXAMPLE)?;
+
+    let gif = dir.join("alice.gif");
+    run_cli(&["qr", "--type", "gif", "--output", gif.to_str().unwrap(), "--max-fragment-len", "10", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(&["format", "--type", "ur", gif.to_str().unwrap()], ALICE_KNOWS_BOB_EXAMPLE)?;
+
+    // A single frame is not enough to reassemble the UR.
+    assert!(run_cli(&["format", frames.join("alice-1.png").to_str().unwrap()]).is_err());
+    Ok(())
+}
+
+/// Save a copy of a QR code image with its data modules scrambled, leaving
+/// the finder patterns in the corners intact.
+fn save_blurred(from: &std::path::Path, to: &std::path::Path) -> Result<()> {
+    let mut image = image::open(from)?.into_luma8();
+    let (width, height) = image.dimensions();
+    for y in height * 3 / 10..height * 7 / 10 {
+        for x in width * 3 / 10..width * 7 / 10 {
+            if (x / 3 + y / 5) % 3 == 0 {
+                let pixel = image.get_pixel_mut(x, y);
+                pixel.0[0] = 255 - pixel.0[0];
+            }
+        }
+    }
+    image.save(to)?;
+    Ok(())
+}
+
+#[test]
+fn test_qr_read_skips_undecodable() -> Result<()> {
+    let dir = TempPath::dir("qr-undecodable");
+    let frames = dir.join("frames");
+    std::fs::create_dir_all(&frames)?;
+    let png = frames.join("alice.png");
+    run_cli(&["qr", "--type", "png", "--output", png.to_str().unwrap(), "--max-fragment-len", "10", ALICE_KNOWS_BOB_EXAMPLE])?;
+
+    // A blurry extra frame does not prevent reassembling the UR.
+    let blurred = dir.join("blurred");
+    std::fs::create_dir_all(&blurred)?;
+    save_blurred(&frames.join("alice-1.png"), &blurred.join("alice.png"))?;
+    std::fs::copy(blurred.join("alice.png"), frames.join("alice-4.png"))?;
+    run_cli_expect(&["format", "--type", "ur", frames.to_str().unwrap()], ALICE_KNOWS_BOB_EXAMPLE)?;
+
+    let error = run_cli(&["format", blurred.to_str().unwrap()]).unwrap_err().to_string();
+    assert!(error.contains("could be decoded"), "{}", error);
+    Ok(())
+}
+
+#[test]
+fn test_qr_directory_without_images() -> Result<()> {
+    // A directory with no images is not read as QR codes.
+    let dir = TempPath::dir("qr-no-images");
+    std::fs::write(dir.join("notes.txt"), "not an image")?;
+    let error = run_cli(&["format", dir.to_str().unwrap()]).unwrap_err().to_string();
+    assert!(!error.contains("QR code"), "{}", error);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use indoc::indoc;
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+/// "Alice" knows "Bob" (a "Person" aged 30) and "Carol" (a "Person" aged 25).
+fn alice_knows_people() -> Result<String> {
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let bob = run_cli(&["assertion", "add", "pred-obj", "known", "isA", "string", "Person", &bob])?;
+    let bob = run_cli(&["assertion", "add", "pred-obj", "string", "age", "number", "30", &bob])?;
+    let carol = run_cli(&["subject", "type", "string", "Carol"])?;
+    let carol = run_cli(&["assertion", "add", "pred-obj", "known", "isA", "string", "Person", &carol])?;
+    let carol = run_cli(&["assertion", "add", "pred-obj", "string", "age", "number", "25", &carol])?;
+    let alice = run_cli(&["subject", "type", "string", "Alice"])?;
+    let alice = run_cli(&["assertion", "add", "pred-obj", "string", "knows", "envelope", &bob, &alice])?;
+    run_cli(&["assertion", "add", "pred-obj", "string", "knows", "envelope", &carol, &alice])
+}
+
+fn query_flat(query: &str, envelope: &str) -> Result<String> {
+    let nodes = run_cli(&["query", query, envelope])?;
+    Ok(nodes.lines().map(|node| run_cli(&["format", node])).collect::<Result<Vec<_>>>()?.join("\n"))
+}
+
+#[test]
+fn test_query_steps() -> Result<()> {
+    let alice = alice_knows_people()?;
+    assert_eq!(query_flat(".knows[*].age", &alice)?, "25\n30");
+    assert_eq!(query_flat(".knows[=Carol].age", &alice)?, "25");
+    assert_eq!(query_flat(".knows[age=30]", &alice)?, indoc!(r#"
+        "Bob" [
+            'isA': "Person"
+            "age": 30
+        ]"#));
+    assert_eq!(query_flat("[knows].knows[isA=Person][age=25]", &alice)?, indoc!(r#"
+        "Carol" [
+            'isA': "Person"
+            "age": 25
+        ]"#));
+    assert_eq!(query_flat("..'isA'", &alice)?, r#""Person""#);
+    assert_eq!(query_flat("..\"isA\"", &alice)?, "");
+    assert_eq!(query_flat(".knows.*[=Person]", &alice)?, r#""Person""#);
+    assert_eq!(query_flat("[isA]", &alice)?, "");
+    Ok(())
+}
+
+#[test]
+fn test_query_wrapped() -> Result<()> {
+    assert_eq!(query_flat(".lastName", CREDENTIAL_EXAMPLE)?, "");
+    assert_eq!(query_flat("{}[isA=\"Certificate of Completion\"].lastName", CREDENTIAL_EXAMPLE)?, r#""Maxwell""#);
+    assert_eq!(query_flat("..continuingEducationUnits", CREDENTIAL_EXAMPLE)?, "1");
+    assert_eq!(query_flat(".signed", CREDENTIAL_EXAMPLE)?, "Signature");
+    Ok(())
+}
+
+#[test]
+fn test_query_digests() -> Result<()> {
+    let alice = alice_knows_people()?;
+    let digests = run_cli(&["query", "--digest", "..age", &alice])?;
+    assert_eq!(digests.split(' ').count(), 2);
+    let elided = run_cli(&["elide", "removing", &digests, &alice])?;
+    run_cli_expect(
+        &["format", &elided],
+        indoc!(r#"
+        "Alice" [
+            "knows": "Bob" [
+                'isA': "Person"
+                "age": ELIDED
+            ]
+            "knows": "Carol" [
+                'isA': "Person"
+                "age": ELIDED
+            ]
+        ]
+        "#)
+    )?;
+    let proof = run_cli(&["proof", "create", &digests, &alice])?;
+    run_cli(&["proof", "confirm", &proof, &digests, &alice])?;
+    Ok(())
+}
+
+#[test]
+fn test_query_errors() -> Result<()> {
+    assert!(run_cli(&["query", ".knows[", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    assert!(run_cli(&["query", "knows", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    assert!(run_cli(&["query", ".'noSuchKnownValue'", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+fn run_script(name: &str, script: &str, args: &[&str]) -> Result<String> {
+    let path = TempPath::file(&format!("run-{}.txt", name));
+    std::fs::write(&path, script)?;
+    let mut cli_args = vec!["run", path.to_str().unwrap()];
+    cli_args.extend(args);
+    run_cli(&cli_args)
+}
+
+#[test]
+fn test_run() -> Result<()> {
+    let script = indoc! {r#"
+        # Alice knows Bob.
+        subject type string Alice
+        assertion add pred-obj string knows string Bob
+    "#};
+    assert_eq!(run_script("basic", script, &[])?, ALICE_KNOWS_BOB_EXAMPLE);
+    Ok(())
+}
+
+#[test]
+fn test_run_variables() -> Result<()> {
+    let script = indoc! {r#"
+        BOB = subject type string Bob
+        subject type string $NAME
+        assertion add pred-obj \
+            string knows envelope $BOB
+        ALICE = digest
+        format
+        digest
+        $BOB
+    "#};
+    let expected = indoc! {r#"
+        "Alice" [
+            "knows": "Bob"
+        ]
+        ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds
+        ur:envelope/tpsoiafwjlidpfzesrbb
+    "#};
+    assert_eq!(run_script("variables", script, &["--var", "NAME=Alice"])?, expected.trim());
+    Ok(())
+}
+
+#[test]
+fn test_run_errors() {
+    assert!(run_script("undefined", "subject type string $MISSING", &[]).is_err());
+    assert!(run_script("no-envelope", "format", &[]).is_err());
+    assert!(run_script("nested", "run script.txt", &[]).is_err());
+
+    // Only envelopes default to the current envelope; other arguments are required.
+    let missing_value = indoc! {r#"
+        subject type string Alice
+        subject type string
+    "#};
+    assert!(run_script("missing-value", missing_value, &[]).is_err());
+    let missing_argument = indoc! {r#"
+        subject type string Alice
+        info
+    "#};
+    assert!(run_script("missing-argument", missing_argument, &[]).is_err());
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_shell() -> Result<()> {
+    let input = indoc! {r#"
+        subject type string Alice
+        assertion add pred-obj string knows string Bob
+        assertion count
+        BOB = extract object
+        undo
+        undo
+        ur:envelope/lftpsoihfpjziniaihoytpsoihjejtjlktjktpsoiafwjlidutgmnnns
+        digest
+        exit
+    "#};
+    let expected = indoc! {r#"
+        "Alice"
+        "Alice" [
+            "knows": "Bob"
+        ]
+        1
+        "Alice"
+        No current envelope
+        "Alice" [
+            "knows": "Bob"
+        ]
+        ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds
+    "#};
+    run_cli_expect_stdin(&["shell"], expected, input)
+}
+
+#[test]
+fn test_shell_initial_envelope() -> Result<()> {
+    let expected = indoc! {r#"
+        "Alice" [
+            "knows": "Bob"
+        ]
+        "knows": "Bob"
+        "Bob"
+    "#};
+    run_cli_expect_stdin(&["shell", ALICE_KNOWS_BOB_EXAMPLE], expected, "assertion at 0\nextract object\n")
+}
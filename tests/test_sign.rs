@@ -78,3 +78,229 @@ fn test_sign_3() -> Result<()> {
     )?;
     Ok(())
 }
+
+#[test]
+fn test_sign_with_xid_document() -> Result<()> {
+    let xid_document = run_cli(&["xid", "new", "--private", "include", "--name", "Alice", ALICE_PRVKEY_BASE])?;
+    let signed = run_cli(&["sign", "--signer", &xid_document, "--record-xid", "--note", "Signed by Alice.", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["format", &signed],
+        indoc!(r#"
+        "Alice" [
+            "knows": "Bob"
+            'signed': {
+                Signature [
+                    "signer": XID(93a4d4e7)
+                    'note': "Signed by Alice."
+                ]
+            } [
+                'signed': Signature
+            ]
+        ]
+        "#)
+    )?;
+    run_cli(&["verify", &signed, "--verifier", ALICE_PUBKEYS])?;
+
+    // A XID document without private keys cannot sign.
+    let public_document = run_cli(&["xid", "new", ALICE_PUBKEYS])?;
+    assert!(run_cli(&["sign", "--signer", &public_document, ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+
+    // Recording the XID requires a XID document signer.
+    assert!(run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--record-xid", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+
+    // `--in` applies to the envelope, not to the signer or verifier.
+    let hex = run_cli(&["--out", "hex", "subject", "type", "envelope", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let signed = run_cli(&["--in", "hex", "--out", "hex", "sign", "--signer", &xid_document, &hex])?;
+    run_cli(&["--in", "hex", "verify", "--silent", "--verifier", &xid_document, &signed])?;
+    Ok(())
+}
+
+#[test]
+fn test_sign_with_xid_document_key() -> Result<()> {
+    let xid_document = run_cli_piped(&[
+        &["xid", "new", "--private", "include", "--name", "Alice", "--allow", "encrypt", ALICE_PRVKEY_BASE],
+        &["xid", "key", "add", "--private", "include", "--name", "Bob", "--allow", "sign", BOB_PRVKEY_BASE],
+        &["xid", "key", "add", "--private", "include", "--name", "Carol", CAROL_PRVKEY_BASE],
+    ])?;
+
+    // Bob and Carol can both sign, so a key must be selected.
+    assert!(run_cli(&["sign", "--signer", &xid_document, ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+
+    let signed = run_cli(&["sign", "--signer", &xid_document, "--key", "Bob", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli(&["verify", &signed, "--verifier", BOB_PUBKEYS])?;
+
+    let signed = run_cli(&["sign", "--signer", &xid_document, "--key", CAROL_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli(&["verify", &signed, "--verifier", CAROL_PUBKEYS])?;
+
+    // Alice's key may only encrypt.
+    assert!(run_cli(&["sign", "--signer", &xid_document, "--key", "Alice", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    assert!(run_cli(&["sign", "--signer", &xid_document, "--key", "Dave", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_verify_with_xid_document() -> Result<()> {
+    let bob_document = run_cli(&["xid", "new", "--name", "Bob", BOB_PUBKEYS])?;
+    let dave_document = run_cli(&["xid", "new", "--name", "Dave", DAVE_PUBKEYS])?;
+    let alice_document = run_cli_piped(&[
+        &["xid", "new", "--name", "Alice", ALICE_PUBKEYS],
+        &["xid", "key", "add", "--name", "Carol", "--allow", "encrypt", CAROL_PUBKEYS],
+        &["xid", "delegate", "add", "--allow", "sign", &bob_document],
+        &["xid", "delegate", "add", "--allow", "elide", &dave_document],
+    ])?;
+
+    // Alice's key holds the Sign privilege, Carol's does not.
+    let signed = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli(&["verify", "--silent", "--verifier", &alice_document, &signed])?;
+    let signed = run_cli(&["sign", "--signer", CAROL_PRVKEY_BASE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(run_cli(&["verify", "--verifier", &alice_document, &signed]).is_err());
+
+    // Bob is a delegate with the Sign privilege, Dave is not.
+    let signed = run_cli(&["sign", "--signer", BOB_PRVKEY_BASE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(run_cli(&["verify", "--verifier", &alice_document, &signed]).is_err());
+    run_cli(&["verify", "--silent", "--follow-delegates", "--verifier", &alice_document, &signed])?;
+    let signed = run_cli(&["sign", "--signer", DAVE_PRVKEY_BASE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(run_cli(&["verify", "--follow-delegates", "--verifier", &alice_document, &signed]).is_err());
+
+    // A XID document is one verifier, however many of its keys signed.
+    let two_keys = run_cli_piped(&[
+        &["xid", "new", "--name", "Alice", ALICE_PUBKEYS],
+        &["xid", "key", "add", "--name", "Carol", CAROL_PUBKEYS],
+    ])?;
+    let signed = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--signer", CAROL_PRVKEY_BASE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli(&["verify", "--silent", "--verifier", &two_keys, &signed])?;
+    assert!(run_cli(&["verify", "--threshold", "2", "--verifier", &two_keys, &signed]).is_err());
+    let signed = run_cli(&["sign", "--signer", BOB_PRVKEY_BASE, &signed])?;
+    run_cli(&["verify", "--silent", "--threshold", "2", "--verifier", &two_keys, "--verifier", BOB_PUBKEYS, &signed])?;
+
+    // A malformed XID document is reported as such.
+    let malformed = run_cli(&["assertion", "add", "pred-obj", "known", "key", "string", "bogus", &alice_document])?;
+    let error = run_cli(&["verify", "--verifier", &malformed, &signed]).unwrap_err().to_string();
+    assert!(error.contains("invalid XID document"), "{}", error);
+    let error = run_cli(&["sign", "--signer", &malformed, ALICE_KNOWS_BOB_EXAMPLE]).unwrap_err().to_string();
+    assert!(error.contains("invalid XID document"), "{}", error);
+    Ok(())
+}
+
+#[test]
+fn test_sign_metadata() -> Result<()> {
+    let xid_document = run_cli(&["xid", "new", "--private", "include", "--name", "Alice", ALICE_PRVKEY_BASE])?;
+    let signed = run_cli(&[
+        "sign",
+        "--signer", &xid_document,
+        "--record-xid",
+        "--record-key-name",
+        "--date", "2024-05-01T12:00:00Z",
+        "--role", "issuer",
+        "--note", "Audited.",
+        "--assertion", "string", "department", "string", "Audit",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    let signed = run_cli(&[
+        "sign",
+        "--signer", BOB_PRVKEY_BASE,
+        "--date", "2024-05-02T09:30:00Z",
+        "--role", "holder",
+        &signed,
+    ])?;
+    run_cli_expect(
+        &["format", &signed],
+        indoc!(r#"
+        "Alice" [
+            "knows": "Bob"
+            'signed': {
+                Signature [
+                    "department": "Audit"
+                    "role": 'issuer'
+                    "signer": XID(93a4d4e7)
+                    'date': 2024-05-01T12:00:00Z
+                    'name': "Alice"
+                    'note': "Audited."
+                ]
+            } [
+                'signed': Signature
+            ]
+            'signed': {
+                Signature [
+                    "role": 'holder'
+                    'date': 2024-05-02T09:30:00Z
+                ]
+            } [
+                'signed': Signature
+            ]
+        ]
+        "#)
+    )?;
+    run_cli_expect(
+        &["verify", "--metadata", "--verifier", BOB_PUBKEYS, &signed],
+        indoc!(r#"
+        Signature [
+            "role": 'holder'
+            'date': 2024-05-02T09:30:00Z
+        ]
+        "#)
+    )?;
+
+    let signed = run_cli(&["sign", "--signer", BOB_PRVKEY_BASE, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(&["verify", "--metadata", "--verifier", BOB_PUBKEYS, &signed], "Signature\n")?;
+
+    // Metadata other than the signer's XID and key name is per signature.
+    assert!(run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--signer", BOB_PRVKEY_BASE, "--date", "now", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    assert!(run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--signer", BOB_PRVKEY_BASE, "--note", "Audited.", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    run_cli(&["sign", "--signer", &xid_document, "--signer", BOB_PRVKEY_BASE, "--record-xid", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(run_cli(&["sign", "--signer", BOB_PRVKEY_BASE, "--role", "unknownRole", ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_sign_detached() -> Result<()> {
+    let signature = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--detached", "--note", "Detached.", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["format", &signature],
+        indoc!(r#"
+        Digest(8955db5e) [
+            'signed': {
+                Signature [
+                    'note': "Detached."
+                ]
+            } [
+                'signed': Signature
+            ]
+        ]
+        "#)
+    )?;
+    run_cli_expect(
+        &["verify", "--detached", &signature, "--verifier", ALICE_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE],
+        ALICE_KNOWS_BOB_EXAMPLE
+    )?;
+    assert!(run_cli(&["verify", "--detached", &signature, "--verifier", BOB_PUBKEYS, ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    let hex = run_cli(&["--out", "hex", "subject", "type", "envelope", ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli(&["--in", "hex", "verify", "--silent", "--detached", &signature, "--verifier", ALICE_PUBKEYS, &hex])?;
+
+    // The signature covers the whole envelope, not only its subject.
+    let changed = run_cli(&["assertion", "add", "pred-obj", "string", "knows", "string", "Carol", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(run_cli(&["verify", "--detached", &signature, "--verifier", ALICE_PUBKEYS, &changed]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_sign_detached_file() -> Result<()> {
+    let path = TempPath::file("sign.bin");
+    std::fs::write(&path, b"A large artifact.")?;
+    let file = path.to_str().unwrap();
+    let signature = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--file", file])?;
+    let digest = run_cli_stdin(&["generate", "digest"], "A large artifact.")?;
+    run_cli_expect(&["extract", "digest", &signature], &digest)?;
+    run_cli(&["verify", "--silent", "--detached", &signature, "--verifier", ALICE_PUBKEYS, "--file", file])?;
+    std::fs::write(&path, b"A tampered artifact.")?;
+    assert!(run_cli(&["verify", "--detached", &signature, "--verifier", ALICE_PUBKEYS, "--file", file]).is_err());
+
+    // A file is signed instead of an envelope, and always detached.
+    let error = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--file", file, ALICE_KNOWS_BOB_EXAMPLE]).unwrap_err().to_string();
+    assert!(error.contains("cannot be used with"), "{}", error);
+    let error = run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, "--detached", "--file", file]).unwrap_err().to_string();
+    assert!(error.contains("cannot be used with"), "{}", error);
+    let error = run_cli(&["verify", "--detached", &signature, "--verifier", ALICE_PUBKEYS, "--file", file, ALICE_KNOWS_BOB_EXAMPLE]).unwrap_err().to_string();
+    assert!(error.contains("cannot be used with"), "{}", error);
+    Ok(())
+}
@@ -46,3 +46,82 @@ fn test_sskr_2() -> Result<()> {
     assert_eq!(restored2, ALICE_KNOWS_BOB_EXAMPLE);
     Ok(())
 }
+
+#[test]
+fn test_sskr_share_recipients() -> Result<()> {
+    let carol_xid = run_cli(&["xid", "new", "--name", "Carol", CAROL_PUBKEYS])?;
+    let result = run_cli(&[
+        "sskr",
+        "split",
+        "-g",
+        "2-of-3",
+        "--recipient",
+        ALICE_PUBKEYS,
+        "--share-recipient",
+        &format!("1.2={}", BOB_PUBKEYS),
+        "--share-recipient",
+        &format!("1.3={}", carol_xid),
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    let shares = result.split_whitespace().collect::<Vec<_>>();
+    run_cli_expect(
+        &["format", shares[0]],
+        indoc!(r#"
+        ENCRYPTED [
+            'hasRecipient': SealedMessage
+            'sskrShare': SSKRShare
+        ]
+        "#)
+    )?;
+    run_cli_expect(
+        &["format", shares[1]],
+        indoc!(r#"
+        ENCRYPTED [
+            'hasRecipient': SealedMessage
+            'holder': PublicKeys(e2c18423)
+        ]
+        "#)
+    )?;
+    assert!(run_cli(&["format", shares[2]])?.contains("'holder': XID("));
+
+    // Each share is sealed to its holder alone.
+    assert!(run_cli(&["decrypt", "--recipient", CAROL_PRVKEY_BASE, shares[1]]).is_err());
+    assert!(run_cli(&["decrypt", "--recipient", ALICE_PRVKEY_BASE, shares[1]]).is_err());
+    let bob_share = run_cli(&["decrypt", "--recipient", BOB_PRVKEY_BASE, shares[1]])?;
+    let carol_share = run_cli(&["decrypt", "--recipient", CAROL_PRVKEY_BASE, shares[2]])?;
+
+    // Opening a share does not reveal the envelope to its holder.
+    let bob_share_content = run_cli(&["extract", "wrapped", &bob_share])?;
+    assert!(run_cli(&["decrypt", "--recipient", BOB_PRVKEY_BASE, &bob_share_content]).is_err());
+
+    assert_eq!(run_cli(&["sskr", "join", &bob_share, &carol_share])?, ALICE_KNOWS_BOB_EXAMPLE);
+    assert_eq!(run_cli(&["sskr", "join", shares[0], &bob_share])?, ALICE_KNOWS_BOB_EXAMPLE);
+    run_cli(&["decrypt", "--recipient", ALICE_PRVKEY_BASE, &bob_share_content])?;
+    Ok(())
+}
+
+#[test]
+fn test_sskr_share_recipient_groups() -> Result<()> {
+    let result = run_cli(&[
+        "sskr",
+        "split",
+        "-g",
+        "1-of-1",
+        "-g",
+        "2-of-2",
+        "--share-recipient",
+        &format!("2={}", BOB_PUBKEYS),
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    let holders = result
+        .split_whitespace()
+        .map(|share| run_cli(&["format", share]).map(|f| f.contains("'holder': PublicKeys(e2c18423)")))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(holders, [false, true, true]);
+
+    for share in ["2", "1.4", "0", "1.x"] {
+        let share_recipient = format!("{}={}", share, BOB_PUBKEYS);
+        assert!(run_cli(&["sskr", "split", "-g", "2-of-3", "--share-recipient", &share_recipient, ALICE_KNOWS_BOB_EXAMPLE]).is_err());
+    }
+    Ok(())
+}
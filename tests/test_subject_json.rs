@@ -0,0 +1,90 @@
+use indoc::indoc;
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+const DOCUMENT: &str = r#"{
+    "@subject": "Alice",
+    "'isA'": { "@type": "known", "@value": "PublicKey" },
+    "knows": ["Bob", "Carol"],
+    "age": 42,
+    "born": { "@type": "date", "@value": "1990-01-01" },
+    "address": { "city": "Springfield", "zip": "12345" }
+}"#;
+
+#[test]
+fn test_subject_json() -> Result<()> {
+    let envelope = run_cli(&["subject", "json", DOCUMENT])?;
+    run_cli_expect(
+        &["format", &envelope],
+        indoc! {r#"
+        "Alice" [
+            'isA': 'PublicKey'
+            "address": {
+                null [
+                    "city": "Springfield"
+                    "zip": "12345"
+                ]
+            }
+            "age": 42
+            "born": 1990-01-01
+            "knows": "Bob"
+            "knows": "Carol"
+        ]
+        "#}
+    )
+}
+
+#[test]
+fn test_subject_json_yaml() -> Result<()> {
+    let yaml = indoc! {r#"
+        "@subject": Alice
+        knows:
+          - Bob
+          - Carol
+    "#};
+    let envelope = run_cli_stdin(&["subject", "json", "--format", "yaml"], yaml)?;
+    run_cli_expect(
+        &["format", &envelope],
+        indoc! {r#"
+        "Alice" [
+            "knows": "Bob"
+            "knows": "Carol"
+        ]
+        "#}
+    )
+}
+
+#[test]
+fn test_subject_json_round_trip() -> Result<()> {
+    let envelope = run_cli(&["subject", "json", DOCUMENT])?;
+    let document = run_cli(&["extract", "json", &envelope])?;
+    run_cli_expect(&["subject", "json", &document], &envelope)?;
+    let yaml = run_cli(&["extract", "yaml", &envelope])?;
+    run_cli_expect(&["subject", "json", "--format", "yaml", &yaml], &envelope)
+}
+
+#[test]
+fn test_subject_json_errors() {
+    let error = run_cli(&["subject", "json", r#"{"knows": "Bob""#]).unwrap_err().to_string();
+    assert!(error.contains("Invalid JSON document"));
+    // Not valid JSON, though valid YAML.
+    assert!(run_cli(&["subject", "json", "knows: Bob"]).is_err());
+
+    let error = run_cli(&["subject", "json", r#"["Bob", "Carol"]"#]).unwrap_err().to_string();
+    assert!(error.contains("Arrays are only supported as the value of an object key"));
+}
+
+#[test]
+fn test_extract_json() -> Result<()> {
+    run_cli_expect(
+        &["extract", "json", ALICE_KNOWS_BOB_EXAMPLE],
+        indoc! {r#"
+        {
+          "@subject": "Alice",
+          "knows": "Bob"
+        }
+        "#}
+    )
+}